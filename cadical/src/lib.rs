@@ -21,14 +21,15 @@
 //! If conflicting CaDiCaL versions are requested, the newest requested version will be selected.
 
 use core::ffi::{c_int, c_void, CStr};
-use std::{cmp::Ordering, ffi::CString, fmt};
+use std::{cmp::Ordering, ffi::CString, fmt, path::Path};
 
 use cpu_time::ProcessTime;
 use ffi::CaDiCaLHandle;
 use rustsat::solvers::{
-    ControlSignal, FreezeVar, GetInternalStats, Interrupt, InterruptSolver, Learn, LimitConflicts,
-    LimitDecisions, PhaseLit, Solve, SolveIncremental, SolveMightFail, SolveStats, SolverError,
-    SolverResult, SolverState, SolverStats, Terminate,
+    ControlSignal, DuplicateClauseTracker, FreezeVar, GetInternalStats, Interrupt, InterruptSolver,
+    Learn, LimitConflicts, LimitDecisions, PhaseLit, ProofLogging, Reset, SetSeed, SetThreads,
+    Solve, SolveIncremental, SolveMightFail, SolveStats, SolverError, SolverResult, SolverState,
+    SolverStats, Terminate,
 };
 use rustsat::types::{Clause, Lit, TernaryVal, Var};
 
@@ -66,6 +67,9 @@ pub struct CaDiCaL<'term, 'learn> {
     terminate_cb: OptTermCallbackStore<'term>,
     learner_cb: OptLearnCallbackStore<'learn>,
     stats: SolverStats,
+    dup_tracker: DuplicateClauseTracker,
+    /// The file the proof is written to, null if proof logging is disabled
+    proof_file: *mut ffi::File,
 }
 
 impl Default for CaDiCaL<'_, '_> {
@@ -76,6 +80,8 @@ impl Default for CaDiCaL<'_, '_> {
             terminate_cb: Default::default(),
             learner_cb: Default::default(),
             stats: Default::default(),
+            dup_tracker: Default::default(),
+            proof_file: std::ptr::null_mut(),
         };
         let quiet = CString::new("quiet").unwrap();
         unsafe { ffi::ccadical_set_option_ret(solver.handle, quiet.as_ptr(), 1) };
@@ -133,7 +139,13 @@ impl CaDiCaL<'_, '_> {
         }
     }
 
-    /// Sets a pre-defined configuration for CaDiCaL's internal options
+    /// Sets a pre-defined configuration for CaDiCaL's internal options. See
+    /// [`Config`] for which configuration to pick for which workload.
+    ///
+    /// Requires state [`SolverState::Configuring`], i.e., the configuration
+    /// must be set before any clauses are added. This is a restriction of
+    /// CaDiCaL itself, since the configuration changes options that can not
+    /// be changed after the solver received input.
     pub fn set_configuration(&mut self, config: Config) -> SolveMightFail {
         if self.state == InternalSolverState::Configuring {
             let config_name = match config {
@@ -434,6 +446,9 @@ impl Solve for CaDiCaL<'_, '_> {
     fn add_clause(&mut self, clause: Clause) -> SolveMightFail {
         // Update wrapper-internal state
         self.stats.n_clauses += 1;
+        if self.dup_tracker.track(&clause) {
+            self.stats.n_duplicate_clauses += 1;
+        }
         self.stats.avg_clause_len =
             (self.stats.avg_clause_len * ((self.stats.n_clauses - 1) as f32) + clause.len() as f32)
                 / self.stats.n_clauses as f32;
@@ -445,6 +460,11 @@ impl Solve for CaDiCaL<'_, '_> {
         unsafe { ffi::ccadical_add(self.handle, 0) };
         Ok(())
     }
+
+    fn warn_duplicate_clauses(&mut self, on: bool) -> SolveMightFail {
+        self.dup_tracker.set_active(on);
+        Ok(())
+    }
 }
 
 impl SolveIncremental for CaDiCaL<'_, '_> {
@@ -712,6 +732,114 @@ impl GetInternalStats for CaDiCaL<'_, '_> {
     }
 }
 
+impl SetThreads for CaDiCaL<'_, '_> {}
+
+impl Reset for CaDiCaL<'_, '_> {
+    fn reset(&mut self) -> Result<(), SolverError> {
+        // The old solver is released when it is dropped
+        *self = Self::default();
+        Ok(())
+    }
+}
+
+impl SetSeed for CaDiCaL<'_, '_> {
+    /// Sets CaDiCaL's `seed` option. Requires state
+    /// [`SolverState::Configuring`].
+    fn set_seed(&mut self, seed: u64) -> Result<(), SolverError> {
+        if self.state != InternalSolverState::Configuring {
+            return Err(SolverError::State(
+                self.state.to_external(),
+                SolverState::Configuring,
+            ));
+        }
+        let seed = c_int::try_from(seed)
+            .map_err(|_| SolverError::Api(format!("seed {} is out of range", seed)))?;
+        self.set_option("seed", seed)
+    }
+}
+
+impl ProofLogging for CaDiCaL<'_, '_> {
+    /// Starts writing a DRAT proof in text format to the file at `path`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rustsat::{lit, solvers::{ProofLogging, Solve}};
+    /// use rustsat_cadical::CaDiCaL;
+    ///
+    /// let path = std::env::temp_dir().join("rustsat-cadical-doc.drat");
+    /// let mut solver = CaDiCaL::default();
+    /// solver.enable_proof(&path).unwrap();
+    /// solver.add_unit(lit![0]).unwrap();
+    /// solver.add_unit(!lit![0]).unwrap();
+    /// solver.solve().unwrap();
+    /// solver.close_proof().unwrap();
+    /// ```
+    fn enable_proof<P: AsRef<Path>>(&mut self, path: P) -> Result<(), SolverError> {
+        if self.state != InternalSolverState::Configuring {
+            return Err(SolverError::State(
+                self.state.to_external(),
+                SolverState::Configuring,
+            ));
+        }
+        if !self.proof_file.is_null() {
+            return Err(SolverError::Api(
+                "proof logging is already enabled".to_string(),
+            ));
+        }
+        let path = path.as_ref();
+        let c_path = match path.to_str().map(CString::new) {
+            Some(Ok(cstr)) => cstr,
+            _ => {
+                return Err(SolverError::Api(format!(
+                    "proof path {} cannot be converted to a C string",
+                    path.display()
+                )))
+            }
+        };
+        // Write the proof in text format rather than binary DRAT
+        self.set_option("binary", 0)?;
+        let mode = CString::new("w").unwrap();
+        let file = unsafe { ffi::fopen(c_path.as_ptr(), mode.as_ptr()) };
+        if file.is_null() {
+            return Err(SolverError::Api(format!(
+                "could not open proof file {}",
+                path.display()
+            )));
+        }
+        unsafe { ffi::ccadical_trace_proof(self.handle, file, c_path.as_ptr()) };
+        self.proof_file = file;
+        Ok(())
+    }
+
+    fn close_proof(&mut self) -> Result<(), SolverError> {
+        if self.proof_file.is_null() {
+            return Err(SolverError::Api("proof logging is not enabled".to_string()));
+        }
+        unsafe {
+            ffi::ccadical_close_proof(self.handle);
+            ffi::fclose(self.proof_file);
+        }
+        self.proof_file = std::ptr::null_mut();
+        Ok(())
+    }
+
+    /// Writes comment lines to the proof file. CaDiCaL writes the proof
+    /// through the same file handle, so comments appear in order with the
+    /// proof steps.
+    fn proof_comment(&mut self, comment: &str) -> Result<(), SolverError> {
+        if self.proof_file.is_null() {
+            return Err(SolverError::Api("proof logging is not enabled".to_string()));
+        }
+        for line in comment.lines() {
+            let line = CString::new(format!("c {}\n", line))
+                .map_err(|_| SolverError::Api("proof comment contains a nul byte".to_string()))?;
+            unsafe { ffi::fputs(line.as_ptr(), self.proof_file) };
+        }
+        Ok(())
+    }
+}
+
 impl SolveStats for CaDiCaL<'_, '_> {
     fn stats(&self) -> SolverStats {
         let max_var_idx = unsafe { ffi::ccadical_vars(self.handle) };
@@ -737,20 +865,32 @@ impl SolveStats for CaDiCaL<'_, '_> {
 
 impl Drop for CaDiCaL<'_, '_> {
     fn drop(&mut self) {
+        if !self.proof_file.is_null() {
+            let _ = self.close_proof();
+        }
         unsafe { ffi::ccadical_release(self.handle) }
     }
 }
 
-/// Possible CaDiCaL configurations
+/// Possible CaDiCaL configurations, mirroring the `--default`, `--plain`,
+/// `--sat` and `--unsat` command line presets of CaDiCaL
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Config {
     /// Set default advanced internal options
     Default,
-    /// Disable all internal preprocessing options
+    /// Disable all internal preprocessing options. Mostly useful for
+    /// debugging or for many very easy incremental calls where preprocessing
+    /// does not pay off.
     Plain,
-    /// Set internal options to target satisfiable instances
+    /// Set internal options to target satisfiable instances. This makes the
+    /// solver stay in stable mode with rephasing and longer restart
+    /// intervals, which helps for workloads where most queries are
+    /// satisfiable, e.g., model enumeration or finding feasible solutions.
     SAT,
-    /// Set internal options to target unsatisfiable instances
+    /// Set internal options to target unsatisfiable instances. This
+    /// disables stable mode and restarts more aggressively, which helps for
+    /// workloads where most queries are unsatisfiable, e.g., proving
+    /// optimality or extracting cores.
     UNSAT,
 }
 
@@ -985,6 +1125,19 @@ mod ffi {
         _private: [u8; 0],
     }
 
+    /// Opaque C `FILE` type
+    #[repr(C)]
+    pub struct File {
+        _private: [u8; 0],
+    }
+
+    extern "C" {
+        // C standard library functions for handling proof files
+        pub fn fopen(path: *const c_char, mode: *const c_char) -> *mut File;
+        pub fn fclose(file: *mut File) -> c_int;
+        pub fn fputs(s: *const c_char, file: *mut File) -> c_int;
+    }
+
     extern "C" {
         // Redefinitions of CaDiCaL C API
         pub fn ccadical_signature() -> *const c_char;
@@ -1037,6 +1190,12 @@ mod ffi {
         pub fn ccadical_propagations(solver: *mut CaDiCaLHandle) -> i64;
         pub fn ccadical_decisions(solver: *mut CaDiCaLHandle) -> i64;
         pub fn ccadical_conflicts(solver: *mut CaDiCaLHandle) -> i64;
+        pub fn ccadical_trace_proof(
+            solver: *mut CaDiCaLHandle,
+            file: *mut File,
+            path: *const c_char,
+        );
+        pub fn ccadical_close_proof(solver: *mut CaDiCaLHandle);
     }
 
     // >= v1.5.4
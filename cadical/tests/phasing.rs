@@ -6,6 +6,7 @@ use rustsat::{
     var,
 };
 use rustsat_cadical::CaDiCaL;
+use rustsat_tools::utils::test_warm_start;
 
 fn test_phase_saving<S: Solve + PhaseLit>(mut solver: S) {
     let inst: SatInstance<BasicVarManager> =
@@ -32,3 +33,12 @@ fn phase_saving() {
     solver.set_option("lucky", 0).unwrap();
     test_phase_saving(solver);
 }
+
+#[test]
+fn warm_start() {
+    let mut first = CaDiCaL::default();
+    first.set_option("lucky", 0).unwrap();
+    let mut second = CaDiCaL::default();
+    second.set_option("lucky", 0).unwrap();
+    test_warm_start(first, second);
+}
@@ -0,0 +1,52 @@
+use rustsat::{
+    clause, lit,
+    solvers::{ClauseRecorder, ProofLogging, Solve, SolverError, SolverResult},
+};
+use rustsat_cadical::CaDiCaL;
+
+#[test]
+fn unsat_proof_ends_with_empty_clause() {
+    let path = std::env::temp_dir().join("rustsat-cadical-unsat.drat");
+    let mut solver = CaDiCaL::default();
+    solver.enable_proof(&path).unwrap();
+    solver.add_binary(lit![0], lit![1]).unwrap();
+    solver.add_binary(lit![0], !lit![1]).unwrap();
+    solver.add_binary(!lit![0], lit![1]).unwrap();
+    solver.add_binary(!lit![0], !lit![1]).unwrap();
+    assert_eq!(solver.solve().unwrap(), SolverResult::Unsat);
+    solver.close_proof().unwrap();
+    let proof = std::fs::read_to_string(&path).unwrap();
+    assert_eq!(proof.lines().last(), Some("0"));
+}
+
+#[test]
+fn recorder_writes_origins_to_proof() {
+    let path = std::env::temp_dir().join("rustsat-cadical-origins.drat");
+    let mut recorder = ClauseRecorder::new(CaDiCaL::default());
+    recorder.enable_proof(&path).unwrap();
+    recorder
+        .add_clause_with_origin(clause![lit![0], !lit![1]], 3)
+        .unwrap();
+    recorder.add_unit(lit![1]).unwrap();
+    recorder
+        .add_clause_with_origin(clause![!lit![0]], 7)
+        .unwrap();
+    assert_eq!(recorder.solve().unwrap(), SolverResult::Unsat);
+    recorder.close_proof().unwrap();
+    let proof = std::fs::read_to_string(&path).unwrap();
+    let comments: Vec<&str> = proof.lines().filter(|l| l.starts_with('c')).collect();
+    assert_eq!(comments, vec!["c origin 3: 1 -2 0", "c origin 7: -1 0"]);
+    assert_eq!(proof.lines().last(), Some("0"));
+}
+
+#[test]
+fn enable_proof_after_solve() {
+    let path = std::env::temp_dir().join("rustsat-cadical-late.drat");
+    let mut solver = CaDiCaL::default();
+    solver.add_unit(lit![0]).unwrap();
+    solver.solve().unwrap();
+    assert!(matches!(
+        solver.enable_proof(&path),
+        Err(SolverError::State(..))
+    ));
+}
@@ -1,6 +1,7 @@
 use rustsat::{
     instances::{BasicVarManager, SatInstance},
-    solvers::{Solve, SolverResult},
+    lit,
+    solvers::{SetSeed, Solve, SolverError, SolverResult},
 };
 use rustsat_cadical::CaDiCaL;
 
@@ -68,3 +69,26 @@ fn unsat_small_unsat() {
         .unwrap();
     small_unsat_instance(solver);
 }
+
+#[test]
+fn same_seed_same_model() {
+    let inst: SatInstance<BasicVarManager> =
+        SatInstance::from_dimacs_path("./data/AProVE11-12.cnf").unwrap();
+    let mut models = vec![];
+    for _ in 0..2 {
+        let mut solver = CaDiCaL::default();
+        solver.set_seed(42).unwrap();
+        solver.add_cnf(inst.clone().as_cnf().0).unwrap();
+        assert_eq!(solver.solve().unwrap(), SolverResult::Sat);
+        models.push(solver.full_solution().unwrap());
+    }
+    assert_eq!(models[0], models[1]);
+}
+
+#[test]
+fn seed_after_solve() {
+    let mut solver = CaDiCaL::default();
+    solver.add_unit(lit![0]).unwrap();
+    solver.solve().unwrap();
+    assert!(matches!(solver.set_seed(42), Err(SolverError::State(..))));
+}
@@ -10,6 +10,14 @@
 //!
 //! The version of Glucose in this crate is Version 4.2.1.
 //! The used C++ source repository can be found [here](https://github.com/chrjabs/glucose4).
+//!
+//! ## Statistics
+//!
+//! Besides [`SolveStats`](rustsat::solvers::SolveStats), the solvers report
+//! propagations, decisions and conflicts via
+//! [`GetInternalStats`](rustsat::solvers::GetInternalStats). The C API of the
+//! used Glucose repository does not expose the number of restarts or the
+//! LBD of learnt clauses, so these statistics are not available.
 
 use rustsat::{solvers::SolverState, types::Lit};
 use std::fmt;
@@ -10,9 +10,9 @@ use cpu_time::ProcessTime;
 use ffi::Glucose4Handle;
 use rustsat::{
     solvers::{
-        GetInternalStats, Interrupt, InterruptSolver, LimitConflicts, LimitPropagations, PhaseLit,
-        Solve, SolveIncremental, SolveMightFail, SolveStats, SolverError, SolverResult,
-        SolverState, SolverStats,
+        DuplicateClauseTracker, GetInternalStats, GetTrail, Interrupt, InterruptSolver,
+        LimitConflicts, LimitPropagations, PhaseLit, Reset, SetThreads, Solve, SolveIncremental,
+        SolveMightFail, SolveStats, SolverError, SolverResult, SolverState, SolverStats,
     },
     types::{Clause, Lit, TernaryVal, Var},
 };
@@ -22,6 +22,7 @@ pub struct Glucose {
     handle: *mut Glucose4Handle,
     state: InternalSolverState,
     stats: SolverStats,
+    dup_tracker: DuplicateClauseTracker,
 }
 
 impl Default for Glucose {
@@ -30,6 +31,7 @@ impl Default for Glucose {
             handle: unsafe { ffi::cglucosesimp4_init() },
             state: Default::default(),
             stats: Default::default(),
+            dup_tracker: Default::default(),
         }
     }
 }
@@ -164,6 +166,9 @@ impl Solve for Glucose {
     fn add_clause(&mut self, clause: Clause) -> SolveMightFail {
         // Update wrapper-internal state
         self.stats.n_clauses += 1;
+        if self.dup_tracker.track(&clause) {
+            self.stats.n_duplicate_clauses += 1;
+        }
         self.stats.avg_clause_len =
             (self.stats.avg_clause_len * ((self.stats.n_clauses - 1) as f32) + clause.len() as f32)
                 / self.stats.n_clauses as f32;
@@ -175,6 +180,11 @@ impl Solve for Glucose {
         unsafe { ffi::cglucosesimp4_add(self.handle, 0) };
         Ok(())
     }
+
+    fn warn_duplicate_clauses(&mut self, on: bool) -> SolveMightFail {
+        self.dup_tracker.set_active(on);
+        Ok(())
+    }
 }
 
 impl SolveIncremental for Glucose {
@@ -297,6 +307,22 @@ impl GetInternalStats for Glucose {
     }
 }
 
+impl GetTrail for Glucose {
+    fn trail_len(&self) -> usize {
+        self.n_assigns().try_into().unwrap()
+    }
+}
+
+impl SetThreads for Glucose {}
+
+impl Reset for Glucose {
+    fn reset(&mut self) -> Result<(), SolverError> {
+        // The old solver is released when it is dropped
+        *self = Self::default();
+        Ok(())
+    }
+}
+
 impl SolveStats for Glucose {
     fn stats(&self) -> SolverStats {
         let mut stats = self.stats.clone();
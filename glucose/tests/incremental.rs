@@ -1,7 +1,7 @@
 use rustsat::{
     instances::{BasicVarManager, SatInstance},
     lit,
-    solvers::{SolveIncremental, SolverResult},
+    solvers::{Solve, SolveIncremental, SolverResult},
 };
 use rustsat_glucose::core::Glucose;
 
@@ -84,3 +84,14 @@ fn assumption_sequence() {
     let solver = Glucose::default();
     test_assumption_sequence(solver);
 }
+
+#[test]
+fn core_after_unsat_assumps() {
+    let mut solver = Glucose::default();
+    solver.add_binary(!lit![0], !lit![1]).unwrap();
+    let res = solver.solve_assumps(&[lit![0], lit![1], lit![2]]).unwrap();
+    assert_eq!(res, SolverResult::Unsat);
+    let mut core = solver.core().unwrap();
+    core.sort();
+    assert_eq!(core, vec![!lit![0], !lit![1]]);
+}
@@ -0,0 +1,27 @@
+use rustsat::{
+    clause, lit,
+    solvers::{GetTrail, Solve, SolverResult},
+};
+use rustsat_glucose::{core, simp};
+
+fn test_trail_len<S: Solve + GetTrail>(mut solver: S) {
+    solver.add_clause(clause![lit![0]]).unwrap();
+    solver.add_clause(clause![!lit![1]]).unwrap();
+    solver.add_clause(clause![!lit![0], lit![2]]).unwrap();
+    solver.add_clause(clause![lit![1], !lit![3]]).unwrap();
+    let res = solver.solve().unwrap();
+    assert_eq!(res, SolverResult::Sat);
+    assert_eq!(solver.trail_len(), 4);
+}
+
+#[test]
+fn core_trail_len() {
+    let solver = core::Glucose::default();
+    test_trail_len(solver);
+}
+
+#[test]
+fn simp_trail_len() {
+    let solver = simp::Glucose::default();
+    test_trail_len(solver);
+}
@@ -30,8 +30,9 @@ use cpu_time::ProcessTime;
 use ffi::KissatHandle;
 use rustsat::{
     solvers::{
-        ControlSignal, Interrupt, InterruptSolver, Solve, SolveMightFail, SolveStats, SolverError,
-        SolverResult, SolverState, SolverStats, Terminate,
+        ControlSignal, DuplicateClauseTracker, Interrupt, InterruptSolver, Reset, SetSeed,
+        SetThreads, Solve, SolveMightFail, SolveStats, SolverError, SolverResult, SolverState,
+        SolverStats, Terminate,
     },
     types::{Clause, Lit, TernaryVal, Var},
 };
@@ -68,6 +69,7 @@ pub struct Kissat<'term> {
     state: InternalSolverState,
     terminate_cb: OptTermCallbackStore<'term>,
     stats: SolverStats,
+    dup_tracker: DuplicateClauseTracker,
 }
 
 impl Default for Kissat<'_> {
@@ -77,6 +79,7 @@ impl Default for Kissat<'_> {
             state: Default::default(),
             terminate_cb: Default::default(),
             stats: Default::default(),
+            dup_tracker: Default::default(),
         };
         let quiet = CString::new("quiet").unwrap();
         unsafe { ffi::kissat_set_option(solver.handle, quiet.as_ptr(), 1) };
@@ -292,6 +295,9 @@ impl Solve for Kissat<'_> {
         }
         // Update wrapper-internal state
         self.stats.n_clauses += 1;
+        if self.dup_tracker.track(&clause) {
+            self.stats.n_duplicate_clauses += 1;
+        }
         self.stats.avg_clause_len =
             (self.stats.avg_clause_len * ((self.stats.n_clauses - 1) as f32) + clause.len() as f32)
                 / self.stats.n_clauses as f32;
@@ -311,6 +317,11 @@ impl Solve for Kissat<'_> {
         unsafe { ffi::kissat_add(self.handle, 0) };
         Ok(())
     }
+
+    fn warn_duplicate_clauses(&mut self, on: bool) -> SolveMightFail {
+        self.dup_tracker.set_active(on);
+        Ok(())
+    }
 }
 
 impl<'term> Terminate<'term> for Kissat<'term> {
@@ -383,6 +394,32 @@ impl InterruptSolver for Interrupter {
     }
 }
 
+impl SetThreads for Kissat<'_> {}
+
+impl Reset for Kissat<'_> {
+    fn reset(&mut self) -> Result<(), SolverError> {
+        // The old solver is released when it is dropped
+        *self = Self::default();
+        Ok(())
+    }
+}
+
+impl SetSeed for Kissat<'_> {
+    /// Sets Kissat's `seed` option. Requires state
+    /// [`SolverState::Configuring`].
+    fn set_seed(&mut self, seed: u64) -> Result<(), SolverError> {
+        if self.state != InternalSolverState::Configuring {
+            return Err(SolverError::State(
+                self.state.to_external(),
+                SolverState::Configuring,
+            ));
+        }
+        let seed = c_int::try_from(seed)
+            .map_err(|_| SolverError::Api(format!("seed {} is out of range", seed)))?;
+        self.set_option("seed", seed)
+    }
+}
+
 impl SolveStats for Kissat<'_> {
     fn stats(&self) -> SolverStats {
         self.stats.clone()
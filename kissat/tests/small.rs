@@ -1,6 +1,8 @@
 use rustsat::{
+    clause,
     instances::{BasicVarManager, SatInstance},
-    solvers::{Solve, SolverResult},
+    lit,
+    solvers::{SetSeed, Solve, SolverError, SolverResult, SolverState},
 };
 use rustsat_kissat::Kissat;
 
@@ -68,3 +70,37 @@ fn unsat_small_unsat() {
         .unwrap();
     small_unsat_instance(solver);
 }
+
+#[test]
+fn add_after_solve() {
+    let mut solver = Kissat::default();
+    solver.add_unit(lit![0]).unwrap();
+    assert_eq!(solver.solve().unwrap(), SolverResult::Sat);
+    assert_eq!(
+        solver.add_clause(clause![lit![1]]),
+        Err(SolverError::State(SolverState::Sat, SolverState::Input))
+    );
+}
+
+#[test]
+fn same_seed_same_model() {
+    let inst: SatInstance<BasicVarManager> =
+        SatInstance::from_dimacs_path("./data/AProVE11-12.cnf").unwrap();
+    let mut models = vec![];
+    for _ in 0..2 {
+        let mut solver = Kissat::default();
+        solver.set_seed(42).unwrap();
+        solver.add_cnf(inst.clone().as_cnf().0).unwrap();
+        assert_eq!(solver.solve().unwrap(), SolverResult::Sat);
+        models.push(solver.full_solution().unwrap());
+    }
+    assert_eq!(models[0], models[1]);
+}
+
+#[test]
+fn seed_after_solve() {
+    let mut solver = Kissat::default();
+    solver.add_unit(lit![0]).unwrap();
+    solver.solve().unwrap();
+    assert!(matches!(solver.set_seed(42), Err(SolverError::State(..))));
+}
@@ -10,6 +10,13 @@
 //!
 //! The version of minisat in this crate is Version 2.2.0.
 //! The used C++ source repository can be found [here](https://github.com/chrjabs/minisat).
+//!
+//! ## Random Seed
+//!
+//! Minisat's `random_seed` option is not exposed by the C API of the used
+//! repository, so the solvers do not implement
+//! [`SetSeed`](rustsat::solvers::SetSeed). With the default options, Minisat
+//! makes no random decisions and is deterministic.
 
 use rustsat::{solvers::SolverState, types::Lit};
 use std::fmt;
@@ -10,9 +10,9 @@ use cpu_time::ProcessTime;
 use ffi::MinisatHandle;
 use rustsat::{
     solvers::{
-        GetInternalStats, Interrupt, InterruptSolver, LimitConflicts, LimitPropagations, PhaseLit,
-        Solve, SolveIncremental, SolveMightFail, SolveStats, SolverError, SolverResult,
-        SolverState, SolverStats,
+        DuplicateClauseTracker, GetInternalStats, GetTrail, Interrupt, InterruptSolver,
+        LimitConflicts, LimitPropagations, PhaseLit, Reset, SetThreads, Solve, SolveIncremental,
+        SolveMightFail, SolveStats, SolverError, SolverResult, SolverState, SolverStats,
     },
     types::{Clause, Lit, TernaryVal, Var},
 };
@@ -22,6 +22,7 @@ pub struct Minisat {
     handle: *mut MinisatHandle,
     state: InternalSolverState,
     stats: SolverStats,
+    dup_tracker: DuplicateClauseTracker,
 }
 
 impl Default for Minisat {
@@ -30,6 +31,7 @@ impl Default for Minisat {
             handle: unsafe { ffi::cminisatsimp_init() },
             state: Default::default(),
             stats: Default::default(),
+            dup_tracker: Default::default(),
         }
     }
 }
@@ -164,6 +166,9 @@ impl Solve for Minisat {
     fn add_clause(&mut self, clause: Clause) -> SolveMightFail {
         // Update wrapper-internal state
         self.stats.n_clauses += 1;
+        if self.dup_tracker.track(&clause) {
+            self.stats.n_duplicate_clauses += 1;
+        }
         self.stats.avg_clause_len =
             (self.stats.avg_clause_len * ((self.stats.n_clauses - 1) as f32) + clause.len() as f32)
                 / self.stats.n_clauses as f32;
@@ -175,6 +180,11 @@ impl Solve for Minisat {
         unsafe { ffi::cminisatsimp_add(self.handle, 0) };
         Ok(())
     }
+
+    fn warn_duplicate_clauses(&mut self, on: bool) -> SolveMightFail {
+        self.dup_tracker.set_active(on);
+        Ok(())
+    }
 }
 
 impl SolveIncremental for Minisat {
@@ -297,6 +307,22 @@ impl GetInternalStats for Minisat {
     }
 }
 
+impl GetTrail for Minisat {
+    fn trail_len(&self) -> usize {
+        self.n_assigns().try_into().unwrap()
+    }
+}
+
+impl SetThreads for Minisat {}
+
+impl Reset for Minisat {
+    fn reset(&mut self) -> Result<(), SolverError> {
+        // The old solver is released when it is dropped
+        *self = Self::default();
+        Ok(())
+    }
+}
+
 impl SolveStats for Minisat {
     fn stats(&self) -> SolverStats {
         let mut stats = self.stats.clone();
@@ -0,0 +1,29 @@
+use rustsat::{
+    clause, lit,
+    solvers::{Solve, SolveStats},
+};
+use rustsat_minisat::{core, simp};
+
+fn test_duplicate_warning<S: Solve + SolveStats>(mut solver: S) {
+    solver.add_clause(clause![lit![0], lit![1]]).unwrap();
+    solver.warn_duplicate_clauses(true).unwrap();
+    solver.add_clause(clause![lit![1], lit![2]]).unwrap();
+    solver.add_clause(clause![lit![2], lit![1]]).unwrap();
+    solver.add_clause(clause![lit![0], !lit![2]]).unwrap();
+    assert_eq!(solver.n_duplicate_clauses(), 1);
+    solver.warn_duplicate_clauses(false).unwrap();
+    solver.add_clause(clause![lit![0], !lit![2]]).unwrap();
+    assert_eq!(solver.n_duplicate_clauses(), 1);
+}
+
+#[test]
+fn core_duplicate_warning() {
+    let solver = core::Minisat::default();
+    test_duplicate_warning(solver);
+}
+
+#[test]
+fn simp_duplicate_warning() {
+    let solver = simp::Minisat::default();
+    test_duplicate_warning(solver);
+}
@@ -0,0 +1,113 @@
+use std::time::{Duration, Instant};
+
+use rustsat::{
+    instances::{BasicVarManager, SatInstance},
+    lit,
+    solvers::{
+        Interrupt, InterruptSolver, LimitConflicts, Solve, SolveIncremental, SolverError,
+        SolverResult,
+    },
+    types::TernaryVal,
+};
+use rustsat_minisat::core::Minisat;
+
+#[test]
+fn resume_in_chunks() {
+    let inst: SatInstance<BasicVarManager> =
+        SatInstance::from_dimacs_path("./data/AProVE11-12.cnf").unwrap();
+    let mut solver = Minisat::default();
+    solver.add_cnf(inst.clone().as_cnf().0).unwrap();
+    let expected = solver.solve().unwrap();
+
+    let mut solver = Minisat::default();
+    solver.add_cnf(inst.as_cnf().0).unwrap();
+    solver.limit_conflicts(Some(10)).unwrap();
+    let mut res = solver.solve().unwrap();
+    while res == SolverResult::Interrupted {
+        solver.limit_conflicts(Some(10)).unwrap();
+        res = solver.resume().unwrap();
+    }
+    assert_eq!(res, expected);
+}
+
+#[test]
+fn resume_assumps_in_chunks() {
+    let inst: SatInstance<BasicVarManager> =
+        SatInstance::from_dimacs_path("./data/AProVE11-12.cnf").unwrap();
+    let assumps = [lit![0], !lit![1]];
+    let mut solver = Minisat::default();
+    solver.add_cnf(inst.clone().as_cnf().0).unwrap();
+    let expected = solver.solve_assumps(&assumps).unwrap();
+
+    let mut solver = Minisat::default();
+    solver.add_cnf(inst.as_cnf().0).unwrap();
+    solver.limit_conflicts(Some(10)).unwrap();
+    let mut res = solver.solve_assumps(&assumps).unwrap();
+    while res == SolverResult::Interrupted {
+        solver.limit_conflicts(Some(10)).unwrap();
+        res = solver.resume_assumps(&assumps).unwrap();
+    }
+    assert_eq!(res, expected);
+    if res == SolverResult::Sat {
+        let sol = solver.full_solution().unwrap();
+        assert_eq!(sol.lit_value(lit![0]), TernaryVal::True);
+        assert_eq!(sol.lit_value(!lit![1]), TernaryVal::True);
+    } else {
+        assert!(!solver.core().unwrap().is_empty());
+    }
+}
+
+#[test]
+fn progress_callback() {
+    let inst: SatInstance<BasicVarManager> =
+        SatInstance::from_dimacs_path("./data/AProVE11-12.cnf").unwrap();
+    let mut solver = Minisat::default();
+    solver.add_cnf(inst.as_cnf().0).unwrap();
+    let mut n_calls = 0;
+    let res = solver
+        .solve_with_progress(1, |stats| {
+            n_calls += 1;
+            assert_eq!(stats.n_terminated, n_calls);
+        })
+        .unwrap();
+    assert_eq!(res, SolverResult::Sat);
+    assert!(n_calls > 0);
+}
+
+#[test]
+fn progress_interrupted() {
+    let inst: SatInstance<BasicVarManager> =
+        SatInstance::from_dimacs_path("./data/AProVE11-12.cnf").unwrap();
+    let mut solver = Minisat::default();
+    solver.add_cnf(inst.as_cnf().0).unwrap();
+    let interrupter = solver.interrupter();
+    let mut n_calls = 0;
+    let res = solver
+        .solve_with_progress(1, |_| {
+            n_calls += 1;
+            interrupter.interrupt();
+        })
+        .unwrap();
+    assert_eq!(res, SolverResult::Interrupted);
+    assert_eq!(n_calls, 1);
+}
+
+#[test]
+fn progress_zero_interval() {
+    let mut solver = Minisat::default();
+    solver.add_binary(lit![0], lit![1]).unwrap();
+    assert!(matches!(
+        solver.solve_with_progress(0, |_| {}),
+        Err(SolverError::Api(_))
+    ));
+}
+
+#[test]
+fn timeout_returns_early() {
+    let mut solver = Minisat::default();
+    solver.add_binary(lit![0], lit![1]).unwrap();
+    let start = Instant::now();
+    let res = solver.solve_timeout(Duration::from_secs(60)).unwrap();
+    assert_eq!(res, SolverResult::Sat);
+    assert!(start.elapsed() < Duration::from_secs(10));
+}
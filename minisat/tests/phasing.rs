@@ -6,6 +6,7 @@ use rustsat::{
     var,
 };
 use rustsat_minisat::{core, simp};
+use rustsat_tools::utils::test_warm_start;
 
 fn test_phase_saving<S: Solve + PhaseLit>(mut solver: S) {
     let inst: SatInstance<BasicVarManager> =
@@ -25,6 +26,7 @@ fn test_phase_saving<S: Solve + PhaseLit>(mut solver: S) {
     solver.unphase_var(var![1]).unwrap();
     solver.unphase_var(var![0]).unwrap();
 }
+
 #[test]
 fn core_phase_saving() {
     let solver = core::Minisat::default();
@@ -36,3 +38,13 @@ fn simp_phase_saving() {
     let solver = simp::Minisat::default();
     test_phase_saving(solver);
 }
+
+#[test]
+fn core_warm_start() {
+    test_warm_start(core::Minisat::default(), core::Minisat::default());
+}
+
+#[test]
+fn simp_warm_start() {
+    test_warm_start(simp::Minisat::default(), simp::Minisat::default());
+}
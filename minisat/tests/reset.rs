@@ -0,0 +1,36 @@
+use rustsat::{
+    lit,
+    solvers::{Reset, Solve, SolveStats, SolverResult},
+};
+
+fn reset_loop<S: Solve + SolveStats + Reset + Default>() {
+    let mut solver = S::default();
+    for round in 0..10 {
+        // Compare against a freshly created solver, alternating between a
+        // satisfiable and an unsatisfiable formula
+        let mut fresh = S::default();
+        for slv in [&mut solver, &mut fresh] {
+            slv.add_binary(lit![0], lit![1]).unwrap();
+            slv.add_unit(!lit![0]).unwrap();
+            if round % 2 == 1 {
+                slv.add_unit(!lit![1]).unwrap();
+            }
+        }
+        let res = fresh.solve().unwrap();
+        assert_eq!(solver.solve().unwrap(), res);
+        solver.reset().unwrap();
+        assert_eq!(solver.max_var(), None);
+        assert_eq!(solver.n_clauses(), 0);
+    }
+    assert_eq!(solver.solve().unwrap(), SolverResult::Sat);
+}
+
+#[test]
+fn reset_core() {
+    reset_loop::<rustsat_minisat::core::Minisat>();
+}
+
+#[test]
+fn reset_simp() {
+    reset_loop::<rustsat_minisat::simp::Minisat>();
+}
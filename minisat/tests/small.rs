@@ -1,6 +1,9 @@
 use rustsat::{
     instances::{BasicVarManager, SatInstance},
-    solvers::{Solve, SolverResult},
+    lit,
+    solvers::{Solve, SolveStats, SolverError, SolverResult, SolverState},
+    types::TernaryVal,
+    var,
 };
 use rustsat_minisat::{core, simp};
 
@@ -49,3 +52,62 @@ fn simp_small_unsat() {
     let res = solver.solve().unwrap();
     assert_eq!(res, SolverResult::Unsat);
 }
+
+fn test_solution_state<S: Solve>(mut solver: S) {
+    solver.add_binary(lit![0], !lit![1]).unwrap();
+    solver.add_unit(!lit![0]).unwrap();
+    assert!(matches!(
+        solver.solution(var![1]),
+        Err(SolverError::State(_, SolverState::Sat))
+    ));
+    assert_eq!(solver.solve().unwrap(), SolverResult::Sat);
+    let sol = solver.solution(var![1]).unwrap();
+    assert_eq!(sol.max_var(), Some(var![1]));
+    assert_eq!(sol.lit_value(lit![0]), TernaryVal::False);
+    assert_eq!(sol.lit_value(lit![1]), TernaryVal::False);
+    solver.add_unit(lit![0]).unwrap();
+    assert_eq!(solver.solve().unwrap(), SolverResult::Unsat);
+    assert!(matches!(
+        solver.solution(var![1]),
+        Err(SolverError::State(SolverState::Unsat, SolverState::Sat))
+    ));
+}
+
+#[test]
+fn core_solution_state() {
+    test_solution_state(core::Minisat::default());
+}
+
+#[test]
+fn simp_solution_state() {
+    test_solution_state(simp::Minisat::default());
+}
+
+fn test_bulk_add_stats<S: Solve + SolveStats + Default>() {
+    let inst: SatInstance<BasicVarManager> =
+        SatInstance::from_dimacs_path("./data/small.cnf").unwrap();
+    let cnf = inst.as_cnf().0;
+    let mut single = S::default();
+    for cl in cnf.clone() {
+        single.add_clause(cl).unwrap();
+    }
+    let mut bulk = S::default();
+    bulk.add_clauses(cnf.clone()).unwrap();
+    let mut bulk_cnf = S::default();
+    bulk_cnf.add_cnf(cnf).unwrap();
+    for solver in [&bulk, &bulk_cnf] {
+        assert_eq!(solver.n_clauses(), single.n_clauses());
+        assert_eq!(solver.avg_clause_len(), single.avg_clause_len());
+        assert_eq!(solver.max_var(), single.max_var());
+    }
+}
+
+#[test]
+fn core_bulk_add_stats() {
+    test_bulk_add_stats::<core::Minisat>();
+}
+
+#[test]
+fn simp_bulk_add_stats() {
+    test_bulk_add_stats::<simp::Minisat>();
+}
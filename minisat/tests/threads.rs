@@ -0,0 +1,12 @@
+use rustsat::solvers::{SetThreads, SolverError};
+use rustsat_minisat::core::Minisat;
+
+#[test]
+fn threads_unsupported() {
+    let mut solver = Minisat::default();
+    assert!(solver.set_threads(1).is_ok());
+    assert!(matches!(
+        solver.set_threads(4),
+        Err(SolverError::NoTypeSupport(..))
+    ));
+}
@@ -9,7 +9,9 @@ use crate::types::Lit;
 pub mod am1;
 pub mod atomics;
 pub mod card;
+pub mod matrix;
 pub mod pb;
+pub mod xor;
 
 /// Trait for collecting clauses. Mainly used when generating encodings and implemented by
 /// [`crate::instances::Cnf`], and solvers.
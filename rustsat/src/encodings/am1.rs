@@ -21,11 +21,20 @@
 //! ```
 
 use super::{CollectClauses, Error};
-use crate::instances::ManageVars;
+use crate::{clause, instances::ManageVars, types::Lit};
 
 mod pairwise;
 pub use pairwise::Pairwise;
 
+mod commander;
+pub use commander::Commander;
+
+mod bimander;
+pub use bimander::Bimander;
+
+mod ladder;
+pub use ladder::Ladder;
+
 /// Trait for all at-most-1 encodings
 pub trait Encode {
     /// Gets the number of literals in the encoding
@@ -47,3 +56,11 @@ pub type Def = Pairwise;
 pub fn new_default_am1() -> impl Encode {
     Def::default()
 }
+
+/// Encodes at-most-1 over a set of literals with the pairwise encoding. Used
+/// for the groups in encodings that split the input literals.
+fn encode_pairwise<Col: CollectClauses>(lits: &[Lit], collector: &mut Col) {
+    collector.extend((0..lits.len()).flat_map(|first| {
+        (first + 1..lits.len()).map(move |second| clause![!lits[first], !lits[second]])
+    }));
+}
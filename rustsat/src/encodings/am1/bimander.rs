@@ -0,0 +1,144 @@
+//! # Bimander At-Most-1 Encoding
+//!
+//! ## References
+//!
+//! - Van-Hau Nguyen and Son Thay Mai: _A New Method to Encode the At-Most-One
+//!   Constraint into SAT_, SoICT 2015.
+
+use super::{encode_pairwise, Encode};
+use crate::{
+    clause,
+    encodings::{CollectClauses, EncodeStats, Error, IterInputs},
+    instances::ManageVars,
+    types::Lit,
+};
+
+/// The default number of literals per group
+const DEFAULT_GROUP_SIZE: usize = 2;
+
+/// Implementation of the bimander at-most-1 encoding. The input literals are
+/// split into `m` groups that are encoded pairwise. Each literal implies the
+/// binary representation of the index of its group over `ceil(log2(m))`
+/// auxiliary variables, which prevents literals from different groups from
+/// being true at the same time.
+///
+/// # References
+///
+/// - Van-Hau Nguyen and Son Thay Mai: _A New Method to Encode the At-Most-One
+///   Constraint into SAT_, SoICT 2015.
+pub struct Bimander {
+    /// Input literals
+    in_lits: Vec<Lit>,
+    /// The number of literals per group
+    group_size: usize,
+    /// The number of variables in the encoding
+    n_vars: u32,
+    /// The number of clauses in the encoding
+    n_clauses: usize,
+}
+
+impl Bimander {
+    /// Sets the number of literals per group. A group size of 1 results in
+    /// the binary at-most-1 encoding.
+    ///
+    /// # Panics
+    ///
+    /// If `group_size` is 0.
+    #[must_use]
+    pub fn with_group_size(mut self, group_size: usize) -> Self {
+        assert!(group_size >= 1, "group size must be at least 1");
+        self.group_size = group_size;
+        self
+    }
+}
+
+impl Default for Bimander {
+    fn default() -> Self {
+        Self {
+            in_lits: Default::default(),
+            group_size: DEFAULT_GROUP_SIZE,
+            n_vars: Default::default(),
+            n_clauses: Default::default(),
+        }
+    }
+}
+
+impl Encode for Bimander {
+    fn n_lits(&self) -> usize {
+        self.in_lits.len()
+    }
+
+    fn encode<Col>(
+        &mut self,
+        collector: &mut Col,
+        var_manager: &mut dyn ManageVars,
+    ) -> Result<(), Error>
+    where
+        Col: CollectClauses,
+    {
+        let prev_clauses = collector.n_clauses();
+        let prev_vars = var_manager.n_used();
+        let n_groups = self.in_lits.len().div_ceil(self.group_size);
+        // ceil(log2(n_groups)) bits are needed to distinguish the groups
+        let n_bits = if n_groups > 1 {
+            (usize::BITS - (n_groups - 1).leading_zeros()) as usize
+        } else {
+            0
+        };
+        let bits: Vec<Lit> = (0..n_bits).map(|_| var_manager.new_lit()).collect();
+        for (group_idx, group) in self.in_lits.chunks(self.group_size).enumerate() {
+            encode_pairwise(group, collector);
+            collector.extend(group.iter().flat_map(|&l| {
+                bits.iter().enumerate().map(move |(bit_idx, &bit)| {
+                    if group_idx & (1 << bit_idx) != 0 {
+                        clause![!l, bit]
+                    } else {
+                        clause![!l, !bit]
+                    }
+                })
+            }));
+        }
+        self.n_clauses = collector.n_clauses() - prev_clauses;
+        self.n_vars = var_manager.n_used() - prev_vars;
+        Ok(())
+    }
+}
+
+impl IterInputs for Bimander {
+    type Iter<'a> = std::iter::Copied<std::slice::Iter<'a, Lit>>;
+
+    fn iter(&self) -> Self::Iter<'_> {
+        self.in_lits.iter().copied()
+    }
+}
+
+impl EncodeStats for Bimander {
+    fn n_clauses(&self) -> usize {
+        self.n_clauses
+    }
+
+    fn n_vars(&self) -> u32 {
+        self.n_vars
+    }
+}
+
+impl From<Vec<Lit>> for Bimander {
+    fn from(lits: Vec<Lit>) -> Self {
+        Self {
+            in_lits: lits,
+            ..Default::default()
+        }
+    }
+}
+
+impl FromIterator<Lit> for Bimander {
+    fn from_iter<T: IntoIterator<Item = Lit>>(iter: T) -> Self {
+        Self::from(Vec::from_iter(iter))
+    }
+}
+
+impl Extend<Lit> for Bimander {
+    fn extend<T: IntoIterator<Item = Lit>>(&mut self, iter: T) {
+        self.in_lits.extend(iter)
+    }
+}
@@ -0,0 +1,151 @@
+//! # Commander At-Most-1 Encoding
+//!
+//! ## References
+//!
+//! - Will Klieber and Gihwon Kwon: _Efficient CNF Encoding for Selecting 1
+//!   from N Objects_, CFV 2007.
+
+use super::{encode_pairwise, Encode};
+use crate::{
+    clause,
+    encodings::{CollectClauses, EncodeStats, Error, IterInputs},
+    instances::ManageVars,
+    types::{Clause, Lit},
+};
+
+/// The default number of literals per group
+const DEFAULT_GROUP_SIZE: usize = 3;
+
+/// Implementation of the commander at-most-1 encoding. The input literals are
+/// split into groups, each group gets a commander variable that is implied by
+/// the literals in the group, and the at-most-1 constraint over the
+/// commanders is encoded recursively. Groups are encoded pairwise.
+///
+/// # References
+///
+/// - Will Klieber and Gihwon Kwon: _Efficient CNF Encoding for Selecting 1
+///   from N Objects_, CFV 2007.
+pub struct Commander {
+    /// Input literals
+    in_lits: Vec<Lit>,
+    /// The number of literals per group
+    group_size: usize,
+    /// The number of variables in the encoding
+    n_vars: u32,
+    /// The number of clauses in the encoding
+    n_clauses: usize,
+}
+
+impl Commander {
+    /// Sets the number of literals per group
+    ///
+    /// # Panics
+    ///
+    /// If `group_size` is smaller than 2.
+    #[must_use]
+    pub fn with_group_size(mut self, group_size: usize) -> Self {
+        assert!(group_size >= 2, "group size must be at least 2");
+        self.group_size = group_size;
+        self
+    }
+
+    /// Recursively encodes the at-most-1 constraint over `lits`
+    fn encode_rec<Col>(
+        lits: &[Lit],
+        group_size: usize,
+        collector: &mut Col,
+        var_manager: &mut dyn ManageVars,
+    ) where
+        Col: CollectClauses,
+    {
+        if lits.len() <= group_size {
+            encode_pairwise(lits, collector);
+            return;
+        }
+        let commanders: Vec<Lit> = lits
+            .chunks(group_size)
+            .map(|group| {
+                encode_pairwise(group, collector);
+                let cmd = var_manager.new_lit();
+                collector.extend(group.iter().map(|&l| clause![!l, cmd]));
+                let mut cl: Clause = group.iter().copied().collect();
+                cl.add(!cmd);
+                collector.extend([cl]);
+                cmd
+            })
+            .collect();
+        Self::encode_rec(&commanders, group_size, collector, var_manager);
+    }
+}
+
+impl Default for Commander {
+    fn default() -> Self {
+        Self {
+            in_lits: Default::default(),
+            group_size: DEFAULT_GROUP_SIZE,
+            n_vars: Default::default(),
+            n_clauses: Default::default(),
+        }
+    }
+}
+
+impl Encode for Commander {
+    fn n_lits(&self) -> usize {
+        self.in_lits.len()
+    }
+
+    fn encode<Col>(
+        &mut self,
+        collector: &mut Col,
+        var_manager: &mut dyn ManageVars,
+    ) -> Result<(), Error>
+    where
+        Col: CollectClauses,
+    {
+        let prev_clauses = collector.n_clauses();
+        let prev_vars = var_manager.n_used();
+        Self::encode_rec(&self.in_lits, self.group_size, collector, var_manager);
+        self.n_clauses = collector.n_clauses() - prev_clauses;
+        self.n_vars = var_manager.n_used() - prev_vars;
+        Ok(())
+    }
+}
+
+impl IterInputs for Commander {
+    type Iter<'a> = std::iter::Copied<std::slice::Iter<'a, Lit>>;
+
+    fn iter(&self) -> Self::Iter<'_> {
+        self.in_lits.iter().copied()
+    }
+}
+
+impl EncodeStats for Commander {
+    fn n_clauses(&self) -> usize {
+        self.n_clauses
+    }
+
+    fn n_vars(&self) -> u32 {
+        self.n_vars
+    }
+}
+
+impl From<Vec<Lit>> for Commander {
+    fn from(lits: Vec<Lit>) -> Self {
+        Self {
+            in_lits: lits,
+            ..Default::default()
+        }
+    }
+}
+
+impl FromIterator<Lit> for Commander {
+    fn from_iter<T: IntoIterator<Item = Lit>>(iter: T) -> Self {
+        Self::from(Vec::from_iter(iter))
+    }
+}
+
+impl Extend<Lit> for Commander {
+    fn extend<T: IntoIterator<Item = Lit>>(&mut self, iter: T) {
+        self.in_lits.extend(iter)
+    }
+}
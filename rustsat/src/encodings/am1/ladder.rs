@@ -0,0 +1,111 @@
+//! # Ladder At-Most-1 Encoding
+//!
+//! ## References
+//!
+//! - Ian P. Gent and Peter Nightingale: _A New Encoding of AllDifferent into
+//!   SAT_, ModRef 2004.
+
+use super::Encode;
+use crate::{
+    clause,
+    encodings::{CollectClauses, EncodeStats, Error, IterInputs},
+    instances::ManageVars,
+    types::Lit,
+};
+
+/// Implementation of the ladder at-most-1 encoding. The encoding introduces a
+/// ladder of `n - 1` auxiliary variables `y_i` with `y_{i+1} -> y_i`. Input
+/// literal `x_i` implies `y_{i-1}` and `!y_i`, i.e., it fixes the position at
+/// which the ladder steps from true to false.
+///
+/// # References
+///
+/// - Ian P. Gent and Peter Nightingale: _A New Encoding of AllDifferent into
+///   SAT_, ModRef 2004.
+#[derive(Default)]
+pub struct Ladder {
+    /// Input literals
+    in_lits: Vec<Lit>,
+    /// The number of variables in the encoding
+    n_vars: u32,
+    /// The number of clauses in the encoding
+    n_clauses: usize,
+}
+
+impl Encode for Ladder {
+    fn n_lits(&self) -> usize {
+        self.in_lits.len()
+    }
+
+    fn encode<Col>(
+        &mut self,
+        collector: &mut Col,
+        var_manager: &mut dyn ManageVars,
+    ) -> Result<(), Error>
+    where
+        Col: CollectClauses,
+    {
+        let prev_clauses = collector.n_clauses();
+        let prev_vars = var_manager.n_used();
+        let n_lits = self.in_lits.len();
+        let ladder: Vec<Lit> = (1..n_lits).map(|_| var_manager.new_lit()).collect();
+        // Ladder clauses
+        collector.extend(ladder.windows(2).map(|w| clause![!w[1], w[0]]));
+        // Channeling clauses
+        collector.extend(
+            self.in_lits
+                .iter()
+                .zip(&ladder)
+                .map(|(&lit, &step)| clause![!lit, !step]),
+        );
+        collector.extend(
+            self.in_lits
+                .iter()
+                .skip(1)
+                .zip(&ladder)
+                .map(|(&lit, &step)| clause![!lit, step]),
+        );
+        self.n_clauses = collector.n_clauses() - prev_clauses;
+        self.n_vars = var_manager.n_used() - prev_vars;
+        Ok(())
+    }
+}
+
+impl IterInputs for Ladder {
+    type Iter<'a> = std::iter::Copied<std::slice::Iter<'a, Lit>>;
+
+    fn iter(&self) -> Self::Iter<'_> {
+        self.in_lits.iter().copied()
+    }
+}
+
+impl EncodeStats for Ladder {
+    fn n_clauses(&self) -> usize {
+        self.n_clauses
+    }
+
+    fn n_vars(&self) -> u32 {
+        self.n_vars
+    }
+}
+
+impl From<Vec<Lit>> for Ladder {
+    fn from(lits: Vec<Lit>) -> Self {
+        Self {
+            in_lits: lits,
+            ..Default::default()
+        }
+    }
+}
+
+impl FromIterator<Lit> for Ladder {
+    fn from_iter<T: IntoIterator<Item = Lit>>(iter: T) -> Self {
+        Self::from(Vec::from_iter(iter))
+    }
+}
+
+impl Extend<Lit> for Ladder {
+    fn extend<T: IntoIterator<Item = Lit>>(&mut self, iter: T) {
+        self.in_lits.extend(iter)
+    }
+}
@@ -12,7 +12,9 @@ use crate::{
     types::Lit,
 };
 
-/// Implementations of the pairwise at-most-1 encoding.
+/// Implementations of the pairwise at-most-1 encoding. Optionally, the
+/// encoding can also enforce that at least one literal is true, see
+/// [`Pairwise::with_exactly_one`].
 ///
 /// # References
 ///
@@ -21,10 +23,23 @@ use crate::{
 pub struct Pairwise {
     /// Input literals
     in_lits: Vec<Lit>,
+    /// Whether to also enforce that at least one literal is true
+    exactly_one: bool,
     /// The number of clauses in the encoding
     n_clauses: usize,
 }
 
+impl Pairwise {
+    /// Additionally enforces that at least one of the input literals is true.
+    /// With a single input literal, this results in a unit clause, without
+    /// input literals, in the empty clause.
+    #[must_use]
+    pub fn with_exactly_one(mut self) -> Self {
+        self.exactly_one = true;
+        self
+    }
+}
+
 impl Encode for Pairwise {
     fn n_lits(&self) -> usize {
         self.in_lits.len()
@@ -44,6 +59,9 @@ impl Encode for Pairwise {
             (first + 1..self.in_lits.len()).map(move |second| clause![!lits[first], !lits[second]])
         });
         collector.extend(clause_iter);
+        if self.exactly_one {
+            collector.extend([self.in_lits.iter().copied().collect()]);
+        }
         self.n_clauses = collector.n_clauses() - prev_clauses;
         Ok(())
     }
@@ -71,7 +89,7 @@ impl From<Vec<Lit>> for Pairwise {
     fn from(lits: Vec<Lit>) -> Self {
         Self {
             in_lits: lits,
-            n_clauses: Default::default(),
+            ..Default::default()
         }
     }
 }
@@ -80,7 +98,7 @@ impl FromIterator<Lit> for Pairwise {
     fn from_iter<T: IntoIterator<Item = Lit>>(iter: T) -> Self {
         Self {
             in_lits: Vec::from_iter(iter),
-            n_clauses: Default::default(),
+            ..Default::default()
         }
     }
 }
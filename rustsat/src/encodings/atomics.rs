@@ -4,6 +4,7 @@ use std::ops::Not;
 
 use crate::{
     clause,
+    instances::Cnf,
     types::{Clause, Lit},
 };
 
@@ -72,3 +73,51 @@ pub fn cube_impl_cube<'all>(a: &'all [Lit], b: &'all [Lit]) -> impl Iterator<Ite
         cl
     })
 }
+
+/// Equivalence of form `a <-> b`
+pub fn encode_equiv(a: Lit, b: Lit) -> Cnf {
+    Cnf::from_iter([lit_impl_lit(a, b), lit_impl_lit(b, a)])
+}
+
+/// If-then-else gate of form `out <-> (cond ? then_lit : else_lit)`. The two
+/// redundant clauses for `then_lit` and `else_lit` having the same value are
+/// included to strengthen propagation.
+pub fn encode_ite(cond: Lit, then_lit: Lit, else_lit: Lit, out: Lit) -> Cnf {
+    Cnf::from_iter([
+        clause![!cond, !then_lit, out],
+        clause![!cond, then_lit, !out],
+        clause![cond, !else_lit, out],
+        clause![cond, else_lit, !out],
+        clause![!then_lit, !else_lit, out],
+        clause![then_lit, else_lit, !out],
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{encode_equiv, encode_ite, lit_impl_lit};
+    use crate::{lit, test_utils::assignment_from_bits};
+
+    #[test]
+    fn impl_and_equiv() {
+        let imp = lit_impl_lit(lit![0], !lit![1]);
+        let equiv = encode_equiv(lit![0], !lit![1]);
+        for bits in 0..4 {
+            let assign = assignment_from_bits(bits, 2);
+            let (a, b) = (bits & 1 != 0, bits & 2 == 0);
+            assert_eq!(imp.is_sat(&assign), !a || b);
+            assert_eq!(equiv.is_satisfied(&assign), a == b);
+        }
+    }
+
+    #[test]
+    fn ite_exhaustive() {
+        let ite = encode_ite(lit![0], lit![1], lit![2], lit![3]);
+        for bits in 0..16 {
+            let assign = assignment_from_bits(bits, 4);
+            let [cond, then_val, else_val, out] = [0, 1, 2, 3].map(|idx| bits & (1 << idx) != 0);
+            let expected = if cond { then_val } else { else_val };
+            assert_eq!(ite.is_satisfied(&assign), out == expected);
+        }
+    }
+}
@@ -31,13 +31,13 @@ use std::{
     ops::{Bound, Range, RangeBounds},
 };
 
-use super::{CollectClauses, Error};
+use super::{CollectClauses, Error, IterInputs};
 use crate::{
     clause,
-    instances::ManageVars,
+    instances::{Cnf, ManageVars},
     types::{
         constraints::{CardConstraint, CardEQConstr, CardLBConstr, CardUBConstr},
-        Clause, Lit,
+        Clause, Lit, RsHashMap,
     },
 };
 
@@ -49,6 +49,12 @@ pub mod simulators;
 pub mod dbtotalizer;
 pub use dbtotalizer::DbTotalizer;
 
+pub mod seqcounter;
+pub use seqcounter::SequentialCounter;
+
+pub mod cardnetwork;
+pub use cardnetwork::CardinalityNetwork;
+
 /// Trait for all cardinality encodings of form `sum of lits <> rhs`
 pub trait Encode {
     /// Gets the number of input literals in the encoding
@@ -362,6 +368,94 @@ pub fn encode_cardinality_constraint<CE: BoundBoth + FromIterator<Lit>, Col: Col
     CE::encode_constr(constr, collector, var_manager).unwrap()
 }
 
+/// Encodes `sum of lits <= k` with a [`Totalizer`]. Returns the clauses of
+/// the encoding and the assumptions that enforce the bound. The bound only
+/// holds while the assumptions are assumed or after adding them as units.
+///
+/// # Errors
+///
+/// Currently never, the result type matches [`encode_atleast_k`] and
+/// [`encode_exactly_k`].
+///
+/// # Examples
+///
+/// ```
+/// # use rustsat::{encodings::card, instances::{BasicVarManager, ManageVars}, lit, var};
+/// let mut var_manager = BasicVarManager::default();
+/// var_manager.increase_next_free(var![3]);
+/// let (cnf, assumps) =
+///     card::encode_atmost_k(vec![lit![0], lit![1], lit![2]], 1, &mut var_manager).unwrap();
+/// assert!(!cnf.is_empty());
+/// assert_eq!(assumps.len(), 1);
+/// ```
+pub fn encode_atmost_k(
+    lits: Vec<Lit>,
+    k: usize,
+    var_manager: &mut dyn ManageVars,
+) -> Result<(Cnf, Vec<Lit>), Error> {
+    let mut enc = Totalizer::from(lits);
+    let mut cnf = Cnf::new();
+    enc.encode_ub(k..=k, &mut cnf, var_manager);
+    let assumps = enc.enforce_ub(k)?;
+    Ok((cnf, assumps))
+}
+
+/// Encodes `sum of lits >= k` with a [`Totalizer`]. Returns the clauses of
+/// the encoding and the assumptions that enforce the bound.
+///
+/// # Errors
+///
+/// [`Error::Unsat`] if `k` is larger than the number of literals.
+pub fn encode_atleast_k(
+    lits: Vec<Lit>,
+    k: usize,
+    var_manager: &mut dyn ManageVars,
+) -> Result<(Cnf, Vec<Lit>), Error> {
+    if k > lits.len() {
+        return Err(Error::Unsat);
+    }
+    if k == 0 {
+        return Ok((Cnf::new(), vec![]));
+    }
+    let mut enc = Totalizer::from(lits);
+    let mut cnf = Cnf::new();
+    enc.encode_lb(k..=k, &mut cnf, var_manager);
+    let assumps = enc.enforce_lb(k)?;
+    Ok((cnf, assumps))
+}
+
+/// Encodes `sum of lits = k` with a [`Totalizer`]. Returns the clauses of
+/// the encoding and the assumptions that enforce the bound.
+///
+/// # Errors
+///
+/// [`Error::Unsat`] if `k` is larger than the number of literals.
+pub fn encode_exactly_k(
+    lits: Vec<Lit>,
+    k: usize,
+    var_manager: &mut dyn ManageVars,
+) -> Result<(Cnf, Vec<Lit>), Error> {
+    if k > lits.len() {
+        return Err(Error::Unsat);
+    }
+    let mut enc = Totalizer::from(lits);
+    let mut cnf = Cnf::new();
+    enc.encode_both(k..=k, &mut cnf, var_manager);
+    let assumps = enc.enforce_eq(k)?;
+    Ok((cnf, assumps))
+}
+
+/// Converts the inputs of a cardinality encoding to the weighted inputs of an
+/// equivalent pseudo-boolean encoding with all weights 1. The weights of
+/// literals occurring multiple times are summed up.
+pub fn card_to_pb<CE: IterInputs + ?Sized>(enc: &CE) -> RsHashMap<Lit, usize> {
+    let mut weights = RsHashMap::default();
+    for lit in enc.iter() {
+        *weights.entry(lit).or_insert(0) += 1;
+    }
+    weights
+}
+
 fn prepare_ub_range<Enc: Encode, R: RangeBounds<usize>>(enc: &Enc, range: R) -> Range<usize> {
     (match range.start_bound() {
         Bound::Included(b) => *b,
@@ -0,0 +1,379 @@
+//! # Cardinality Network Encoding
+//!
+//! Implementation of the cardinality network encoding \[1\]. The input literals
+//! are sorted with odd-even merge sorting networks that are truncated to the
+//! outputs needed for the requested bounds. Comparators are cached by their
+//! inputs, so extending the network for a larger bound reuses all comparators
+//! that were already built.
+//!
+//! ## References
+//!
+//! - \[1\] Roberto Asín and Robert Nieuwenhuis and Albert Oliveras and Enric
+//!   Rodríguez-Carbonell: _Cardinality Networks: a theoretical and empirical
+//!   study_, Constraints 2011.
+
+use super::{
+    BoundLower, BoundLowerIncremental, BoundUpper, BoundUpperIncremental, Encode,
+    EncodeIncremental, Error,
+};
+use crate::{
+    clause,
+    encodings::{CollectClauses, EncodeStats, IterInputs},
+    instances::ManageVars,
+    types::{Lit, RsHashMap},
+};
+use std::{cmp, ops::RangeBounds};
+
+/// Implementation of the cardinality network encoding \[1\]. Only the part of
+/// the network needed for the encoded bounds is built. The encoding is
+/// incremental in the sense that extending the bounds or the input literals
+/// reuses all comparators whose inputs did not change.
+///
+/// # References
+///
+/// - \[1\] Roberto Asín and Robert Nieuwenhuis and Albert Oliveras and Enric
+///   Rodríguez-Carbonell: _Cardinality Networks: a theoretical and empirical
+///   study_, Constraints 2011.
+#[derive(Default)]
+pub struct CardinalityNetwork {
+    /// Input literals to the network
+    in_lits: Vec<Lit>,
+    /// The built comparators mapping their (ordered) inputs to the maximum
+    /// and minimum output
+    comparators: RsHashMap<(Lit, Lit), (Lit, Lit)>,
+    /// The sorted outputs of the network for the current input literals
+    outputs: Vec<Lit>,
+    /// The number of variables in the encoding
+    n_vars: u32,
+    /// The number of clauses in the encoding
+    n_clauses: usize,
+}
+
+impl CardinalityNetwork {
+    /// Makes sure that at least the `n_outputs` largest outputs of the network
+    /// are encoded
+    fn extend_outputs<Col>(
+        &mut self,
+        n_outputs: usize,
+        collector: &mut Col,
+        var_manager: &mut dyn ManageVars,
+    ) where
+        Col: CollectClauses,
+    {
+        let n_outputs = cmp::min(n_outputs, self.in_lits.len());
+        if n_outputs <= self.outputs.len() {
+            return;
+        }
+        let n_vars_before = var_manager.n_used();
+        let n_clauses_before = collector.n_clauses();
+        let lits: Vec<Option<Lit>> = self.in_lits.iter().copied().map(Some).collect();
+        let mut net = Network {
+            comparators: &mut self.comparators,
+            collector,
+            var_manager,
+        };
+        self.outputs = net
+            .sort(&lits, n_outputs)
+            .into_iter()
+            .map(|out| out.expect("sorted input literals should not be constant"))
+            .collect();
+        self.n_clauses += collector.n_clauses() - n_clauses_before;
+        self.n_vars += var_manager.n_used() - n_vars_before;
+    }
+}
+
+/// Helper for building the sorting network. A `None` literal represents the
+/// constant `false`.
+struct Network<'a, Col> {
+    comparators: &'a mut RsHashMap<(Lit, Lit), (Lit, Lit)>,
+    collector: &'a mut Col,
+    var_manager: &'a mut dyn ManageVars,
+}
+
+impl<Col: CollectClauses> Network<'_, Col> {
+    /// Sorts the literals in descending order and returns the `n_outputs`
+    /// largest outputs
+    fn sort(&mut self, lits: &[Option<Lit>], n_outputs: usize) -> Vec<Option<Lit>> {
+        if lits.len() <= 1 {
+            return lits.to_vec();
+        }
+        let split = lits.len() / 2;
+        let mut left = self.sort(&lits[..split], n_outputs);
+        let mut right = self.sort(&lits[split..], n_outputs);
+        // The largest outputs only depend on the largest outputs of the halves,
+        // so both can be padded with constant false literals
+        let len = cmp::max(left.len(), right.len()).next_power_of_two();
+        left.resize(len, None);
+        right.resize(len, None);
+        let mut merged = self.merge(&left, &right, n_outputs);
+        merged.truncate(cmp::min(n_outputs, lits.len()));
+        merged
+    }
+
+    /// Merges two sorted power-of-two sized sequences of equal length and
+    /// returns the `n_outputs` largest outputs
+    fn merge(
+        &mut self,
+        left: &[Option<Lit>],
+        right: &[Option<Lit>],
+        n_outputs: usize,
+    ) -> Vec<Option<Lit>> {
+        debug_assert_eq!(left.len(), right.len());
+        let len = left.len();
+        if n_outputs == 0 {
+            return vec![];
+        }
+        if len == 1 {
+            let (max, min) = self.compare(left[0], right[0]);
+            let mut merged = vec![max, min];
+            merged.truncate(n_outputs);
+            return merged;
+        }
+        let evens = |lits: &[Option<Lit>]| lits.iter().copied().step_by(2).collect::<Vec<_>>();
+        let odds =
+            |lits: &[Option<Lit>]| lits.iter().copied().skip(1).step_by(2).collect::<Vec<_>>();
+        let even = self.merge(
+            &evens(left),
+            &evens(right),
+            cmp::min(len, n_outputs / 2 + 1),
+        );
+        let odd = self.merge(&odds(left), &odds(right), cmp::min(len, n_outputs / 2));
+        let mut merged = Vec::with_capacity(cmp::min(2 * len, n_outputs));
+        merged.push(even[0]);
+        for idx in 0..len - 1 {
+            if merged.len() >= n_outputs {
+                break;
+            }
+            let (max, min) = self.compare(odd[idx], even[idx + 1]);
+            merged.push(max);
+            merged.push(min);
+        }
+        if merged.len() < n_outputs {
+            merged.push(odd[len - 1]);
+        }
+        merged.truncate(n_outputs);
+        merged
+    }
+
+    /// Encodes a comparator returning the maximum and the minimum of two
+    /// literals
+    fn compare(&mut self, a: Option<Lit>, b: Option<Lit>) -> (Option<Lit>, Option<Lit>) {
+        match (a, b) {
+            (None, x) | (x, None) => (x, None),
+            (Some(a), Some(b)) => {
+                let key = (cmp::min(a, b), cmp::max(a, b));
+                if let Some(&(max, min)) = self.comparators.get(&key) {
+                    return (Some(max), Some(min));
+                }
+                let max = self.var_manager.new_lit();
+                let min = self.var_manager.new_lit();
+                self.collector.extend([
+                    clause![!a, max],
+                    clause![!b, max],
+                    clause![!a, !b, min],
+                    clause![!max, a, b],
+                    clause![!min, a],
+                    clause![!min, b],
+                ]);
+                self.comparators.insert(key, (max, min));
+                (Some(max), Some(min))
+            }
+        }
+    }
+}
+
+impl Encode for CardinalityNetwork {
+    fn n_lits(&self) -> usize {
+        self.in_lits.len()
+    }
+}
+
+impl IterInputs for CardinalityNetwork {
+    type Iter<'a> = super::totalizer::TotIter<'a>;
+
+    fn iter(&self) -> Self::Iter<'_> {
+        self.in_lits.iter().copied()
+    }
+}
+
+impl EncodeIncremental for CardinalityNetwork {
+    /// The variables of the network depend on the encoded bounds, so nothing
+    /// is reserved ahead of time
+    fn reserve(&mut self, _var_manager: &mut dyn ManageVars) {}
+}
+
+impl BoundUpper for CardinalityNetwork {
+    fn encode_ub<Col, R>(&mut self, range: R, collector: &mut Col, var_manager: &mut dyn ManageVars)
+    where
+        Col: CollectClauses,
+        R: RangeBounds<usize>,
+    {
+        let range = super::prepare_ub_range(self, range);
+        if range.is_empty() {
+            return;
+        }
+        // Enforcing an upper bound `ub` requires output `ub`
+        self.extend_outputs(range.end, collector, var_manager);
+    }
+
+    fn enforce_ub(&self, ub: usize) -> Result<Vec<Lit>, Error> {
+        if ub >= self.in_lits.len() {
+            return Ok(vec![]);
+        }
+        match self.outputs.get(ub) {
+            Some(&olit) => Ok(vec![!olit]),
+            None => Err(Error::NotEncoded),
+        }
+    }
+}
+
+impl BoundUpperIncremental for CardinalityNetwork {
+    fn encode_ub_change<Col, R>(
+        &mut self,
+        range: R,
+        collector: &mut Col,
+        var_manager: &mut dyn ManageVars,
+    ) where
+        Col: CollectClauses,
+        R: RangeBounds<usize>,
+    {
+        self.encode_ub(range, collector, var_manager);
+    }
+}
+
+impl BoundLower for CardinalityNetwork {
+    fn encode_lb<Col, R>(&mut self, range: R, collector: &mut Col, var_manager: &mut dyn ManageVars)
+    where
+        Col: CollectClauses,
+        R: RangeBounds<usize>,
+    {
+        let range = super::prepare_lb_range(self, range);
+        if range.is_empty() {
+            return;
+        }
+        // Enforcing a lower bound `lb` requires output `lb - 1`
+        self.extend_outputs(range.end - 1, collector, var_manager);
+    }
+
+    fn enforce_lb(&self, lb: usize) -> Result<Vec<Lit>, Error> {
+        if lb > self.in_lits.len() {
+            return Err(Error::Unsat);
+        }
+        if lb == 0 {
+            return Ok(vec![]);
+        }
+        match self.outputs.get(lb - 1) {
+            Some(&olit) => Ok(vec![olit]),
+            None => Err(Error::NotEncoded),
+        }
+    }
+}
+
+impl BoundLowerIncremental for CardinalityNetwork {
+    fn encode_lb_change<Col, R>(
+        &mut self,
+        range: R,
+        collector: &mut Col,
+        var_manager: &mut dyn ManageVars,
+    ) where
+        Col: CollectClauses,
+        R: RangeBounds<usize>,
+    {
+        self.encode_lb(range, collector, var_manager);
+    }
+}
+
+impl EncodeStats for CardinalityNetwork {
+    fn n_clauses(&self) -> usize {
+        self.n_clauses
+    }
+
+    fn n_vars(&self) -> u32 {
+        self.n_vars
+    }
+}
+
+impl From<Vec<Lit>> for CardinalityNetwork {
+    fn from(lits: Vec<Lit>) -> Self {
+        Self {
+            in_lits: lits,
+            ..Default::default()
+        }
+    }
+}
+
+impl FromIterator<Lit> for CardinalityNetwork {
+    fn from_iter<T: IntoIterator<Item = Lit>>(iter: T) -> Self {
+        Self::from(Vec::from_iter(iter))
+    }
+}
+
+impl Extend<Lit> for CardinalityNetwork {
+    fn extend<T: IntoIterator<Item = Lit>>(&mut self, iter: T) {
+        self.in_lits.extend(iter);
+        // The outputs need to be rebuilt for the new inputs
+        self.outputs.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CardinalityNetwork;
+    use crate::{
+        encodings::{
+            card::{BoundBoth, BoundLower, BoundUpper, Encode},
+            EncodeStats, Error,
+        },
+        instances::{BasicVarManager, Cnf, ManageVars},
+        lit,
+        test_utils::extendable,
+        var,
+    };
+
+    #[test]
+    fn both_exhaustive() {
+        let mut enc = CardinalityNetwork::from(vec![lit![0], lit![1], lit![2], lit![3], lit![4]]);
+        let mut var_manager = BasicVarManager::default();
+        var_manager.increase_next_free(var![5]);
+        let mut cnf = Cnf::new();
+        enc.encode_both(1..3, &mut cnf, &mut var_manager);
+        assert_eq!(enc.enforce_ub(3), Err(Error::NotEncoded));
+        let n_clauses = cnf.len();
+        enc.encode_both(0..3, &mut cnf, &mut var_manager);
+        assert_eq!(cnf.len(), n_clauses);
+        enc.encode_both(0..6, &mut cnf, &mut var_manager);
+        assert_eq!(enc.n_clauses(), cnf.len());
+        assert_eq!(enc.n_vars(), var_manager.n_used() - 5);
+        let n_vars = var_manager.n_used();
+        for b in 0..=enc.n_lits() {
+            let ub_assumps = enc.enforce_ub(b).unwrap();
+            let lb_assumps = enc.enforce_lb(b).unwrap();
+            for inputs in 0u32..1 << 5 {
+                let count = inputs.count_ones() as usize;
+                assert_eq!(extendable(&cnf, &ub_assumps, inputs, 5, n_vars), count <= b);
+                assert_eq!(extendable(&cnf, &lb_assumps, inputs, 5, n_vars), count >= b);
+            }
+        }
+        assert_eq!(enc.enforce_lb(6), Err(Error::Unsat));
+    }
+
+    #[test]
+    fn extend_inputs() {
+        let mut enc = CardinalityNetwork::from(vec![lit![0], lit![1], lit![2]]);
+        let mut var_manager = BasicVarManager::default();
+        var_manager.increase_next_free(var![4]);
+        let mut cnf = Cnf::new();
+        enc.encode_ub(1..2, &mut cnf, &mut var_manager);
+        enc.extend([lit![3]]);
+        assert_eq!(enc.enforce_ub(1), Err(Error::NotEncoded));
+        enc.encode_ub(1..2, &mut cnf, &mut var_manager);
+        let assumps = enc.enforce_ub(1).unwrap();
+        let n_vars = var_manager.n_used();
+        for inputs in 0u32..1 << 4 {
+            assert_eq!(
+                extendable(&cnf, &assumps, inputs, 4, n_vars),
+                inputs.count_ones() <= 1
+            );
+        }
+    }
+}
@@ -0,0 +1,195 @@
+//! # Sequential Counter Encoding
+//!
+//! Implementation of the sequential counter encoding \[1\]. The encoding
+//! introduces a register `s[i][j]` for every prefix of the input literals
+//! meaning that at least `j` of the first `i` input literals are true. For an
+//! upper bound `k`, the encoding has `O(n * k)` clauses and variables, which
+//! makes it preferable over the totalizer for constraints with small bounds.
+//!
+//! ## References
+//!
+//! - \[1\] Carsten Sinz: _Towards an Optimal CNF Encoding of Boolean
+//!   Cardinality Constraints_, CP 2005.
+
+use super::{BoundUpper, Encode, Error};
+use crate::{
+    clause,
+    encodings::{CollectClauses, EncodeStats, IterInputs},
+    instances::ManageVars,
+    types::Lit,
+};
+use std::{cmp, ops::RangeBounds};
+
+/// Implementation of the sequential counter encoding \[1\]. This encoding only
+/// supports upper bounding. Lower bounding can be achieved with
+/// [`super::simulators::Inverted`] and both bounds with
+/// [`super::simulators::Double`]. The registers are built lazily, so encoding a
+/// larger bound extends the existing registers.
+///
+/// # References
+///
+/// - \[1\] Carsten Sinz: _Towards an Optimal CNF Encoding of Boolean
+///   Cardinality Constraints_, CP 2005.
+#[derive(Default)]
+pub struct SequentialCounter {
+    /// Input literals to the encoding
+    in_lits: Vec<Lit>,
+    /// The registers. `regs[i][j]` is implied if at least `j + 1` of the first
+    /// `i + 1` input literals are true.
+    regs: Vec<Vec<Lit>>,
+    /// The number of register columns that have been requested
+    n_cols: usize,
+    /// The number of variables in the encoding
+    n_vars: u32,
+    /// The number of clauses in the encoding
+    n_clauses: usize,
+}
+
+impl SequentialCounter {
+    /// Extends all registers to the currently requested number of columns
+    fn extend_regs<Col>(&mut self, collector: &mut Col, var_manager: &mut dyn ManageVars)
+    where
+        Col: CollectClauses,
+    {
+        for idx in 0..self.in_lits.len() {
+            if idx == self.regs.len() {
+                self.regs.push(vec![]);
+            }
+            let n_cols = cmp::min(self.n_cols, idx + 1);
+            let lit = self.in_lits[idx];
+            while self.regs[idx].len() < n_cols {
+                let col = self.regs[idx].len();
+                let reg = var_manager.new_lit();
+                if col == 0 {
+                    collector.extend([clause![!lit, reg]]);
+                } else {
+                    // At least `col` of the previous literals and the current
+                    // literal is true
+                    let prev = self.regs[idx - 1][col - 1];
+                    collector.extend([clause![!lit, !prev, reg]]);
+                }
+                if col < idx {
+                    // At least `col + 1` of the previous literals are true
+                    let prev = self.regs[idx - 1][col];
+                    collector.extend([clause![!prev, reg]]);
+                }
+                self.regs[idx].push(reg);
+            }
+        }
+    }
+}
+
+impl Encode for SequentialCounter {
+    fn n_lits(&self) -> usize {
+        self.in_lits.len()
+    }
+}
+
+impl IterInputs for SequentialCounter {
+    type Iter<'a> = super::totalizer::TotIter<'a>;
+
+    fn iter(&self) -> Self::Iter<'_> {
+        self.in_lits.iter().copied()
+    }
+}
+
+impl BoundUpper for SequentialCounter {
+    fn encode_ub<Col, R>(&mut self, range: R, collector: &mut Col, var_manager: &mut dyn ManageVars)
+    where
+        Col: CollectClauses,
+        R: RangeBounds<usize>,
+    {
+        let range = super::prepare_ub_range(self, range);
+        if range.is_empty() {
+            return;
+        }
+        let n_vars_before = var_manager.n_used();
+        let n_clauses_before = collector.n_clauses();
+        // Enforcing an upper bound `ub` requires the register for `ub + 1`
+        self.n_cols = cmp::max(self.n_cols, range.end);
+        self.extend_regs(collector, var_manager);
+        self.n_clauses += collector.n_clauses() - n_clauses_before;
+        self.n_vars += var_manager.n_used() - n_vars_before;
+    }
+
+    fn enforce_ub(&self, ub: usize) -> Result<Vec<Lit>, Error> {
+        if ub >= self.in_lits.len() {
+            return Ok(vec![]);
+        }
+        if self.regs.len() < self.in_lits.len() {
+            return Err(Error::NotEncoded);
+        }
+        match self.regs[self.in_lits.len() - 1].get(ub) {
+            Some(&reg) => Ok(vec![!reg]),
+            None => Err(Error::NotEncoded),
+        }
+    }
+}
+
+impl EncodeStats for SequentialCounter {
+    fn n_clauses(&self) -> usize {
+        self.n_clauses
+    }
+
+    fn n_vars(&self) -> u32 {
+        self.n_vars
+    }
+}
+
+impl From<Vec<Lit>> for SequentialCounter {
+    fn from(lits: Vec<Lit>) -> Self {
+        Self {
+            in_lits: lits,
+            ..Default::default()
+        }
+    }
+}
+
+impl FromIterator<Lit> for SequentialCounter {
+    fn from_iter<T: IntoIterator<Item = Lit>>(iter: T) -> Self {
+        Self::from(Vec::from_iter(iter))
+    }
+}
+
+impl Extend<Lit> for SequentialCounter {
+    fn extend<T: IntoIterator<Item = Lit>>(&mut self, iter: T) {
+        self.in_lits.extend(iter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SequentialCounter;
+    use crate::{
+        encodings::{
+            card::{BoundUpper, Encode},
+            EncodeStats, Error,
+        },
+        instances::{BasicVarManager, Cnf, ManageVars},
+        lit,
+        test_utils::extendable,
+        var,
+    };
+
+    #[test]
+    fn ub_exhaustive() {
+        let mut enc = SequentialCounter::from(vec![lit![0], lit![1], lit![2], lit![3]]);
+        let mut var_manager = BasicVarManager::default();
+        var_manager.increase_next_free(var![4]);
+        let mut cnf = Cnf::new();
+        enc.encode_ub(0..2, &mut cnf, &mut var_manager);
+        assert_eq!(enc.enforce_ub(2), Err(Error::NotEncoded));
+        enc.encode_ub(2..4, &mut cnf, &mut var_manager);
+        assert_eq!(enc.n_clauses(), cnf.len());
+        assert_eq!(enc.n_vars(), var_manager.n_used() - 4);
+        let n_vars = var_manager.n_used();
+        for ub in 0..enc.n_lits() {
+            let assumps = enc.enforce_ub(ub).unwrap();
+            for inputs in 0u32..1 << 4 {
+                let sat = extendable(&cnf, &assumps, inputs, 4, n_vars);
+                assert_eq!(sat, inputs.count_ones() as usize <= ub);
+            }
+        }
+        assert!(enc.enforce_ub(4).unwrap().is_empty());
+    }
+}
@@ -19,7 +19,7 @@ use super::{
 use crate::{
     encodings::{atomics, CollectClauses, EncodeStats, IterInputs},
     instances::ManageVars,
-    types::Lit,
+    types::{Lit, Var},
 };
 use std::{
     cmp,
@@ -43,6 +43,8 @@ pub struct Totalizer {
     not_enc_idx: usize,
     /// The root of the tree, if constructed
     root: Option<Node>,
+    /// The auxiliary variables allocated by the totalizer
+    aux_vars: Vec<Var>,
     /// The number of variables in the totalizer
     n_vars: u32,
     /// The number of clauses in the totalizer
@@ -82,6 +84,48 @@ impl Totalizer {
         }
     }
 
+    /// Gets all auxiliary variables allocated by the encoding so far. The input
+    /// literals are not included.
+    pub fn aux_vars(&self) -> &[Var] {
+        &self.aux_vars
+    }
+
+    /// Updates the list of auxiliary variables from the tree
+    fn update_aux_vars(&mut self) {
+        self.aux_vars.clear();
+        if let Some(root) = &self.root {
+            root.collect_aux_vars(&mut self.aux_vars);
+        }
+    }
+
+    /// Gets the output literals of the totalizer, where `output[i]` represents
+    /// that at least `i + 1` input literals are true. Whether an output is
+    /// implied by its inputs or implies them depends on whether it was built
+    /// for upper or lower bounding. Since outputs are built lazily, only the
+    /// outputs up to the first one that has not been built are returned.
+    ///
+    /// # Errors
+    ///
+    /// [`Error::NotEncoded`] if not all input literals have been encoded or
+    /// the first output has not been built.
+    pub fn output_lits(&self) -> Result<Vec<Lit>, Error> {
+        if self.not_enc_idx != self.in_lits.len() {
+            return Err(Error::NotEncoded);
+        };
+        match &self.root {
+            None => Err(Error::NotEncoded),
+            Some(Node::Leaf { lit }) => Ok(vec![*lit]),
+            Some(Node::Internal { out_lits, .. }) => {
+                let outputs: Vec<Lit> = out_lits.iter().map_while(|&ol| ol).collect();
+                if outputs.is_empty() {
+                    Err(Error::NotEncoded)
+                } else {
+                    Ok(outputs)
+                }
+            }
+        }
+    }
+
     /// Gets the maximum depth of the tree
     pub fn depth(&self) -> usize {
         match &self.root {
@@ -117,6 +161,7 @@ impl EncodeIncremental for Totalizer {
         if let Some(root) = &mut self.root {
             root.reserve_all_vars_rec(var_manager);
         }
+        self.update_aux_vars();
     }
 }
 
@@ -141,6 +186,7 @@ impl BoundUpper for Totalizer {
                 self.n_vars += var_manager.n_used() - n_vars_before;
             }
         }
+        self.update_aux_vars();
     }
 
     fn enforce_ub(&self, ub: usize) -> Result<Vec<Lit>, Error> {
@@ -189,6 +235,7 @@ impl BoundLower for Totalizer {
                 self.n_vars += var_manager.n_used() - n_vars_before;
             }
         }
+        self.update_aux_vars();
     }
 
     fn enforce_lb(&self, lb: usize) -> Result<Vec<Lit>, Error> {
@@ -243,6 +290,7 @@ impl BoundUpperIncremental for Totalizer {
                 self.n_vars += var_manager.n_used() - n_vars_before;
             }
         }
+        self.update_aux_vars();
     }
 }
 
@@ -271,6 +319,7 @@ impl BoundLowerIncremental for Totalizer {
                 self.n_vars += var_manager.n_used() - n_vars_before;
             }
         }
+        self.update_aux_vars();
     }
 }
 
@@ -290,6 +339,7 @@ impl From<Vec<Lit>> for Totalizer {
             in_lits: lits,
             not_enc_idx: Default::default(),
             root: Default::default(),
+            aux_vars: Default::default(),
             n_vars: Default::default(),
             n_clauses: Default::default(),
         }
@@ -302,6 +352,7 @@ impl FromIterator<Lit> for Totalizer {
             in_lits: Vec::from_iter(iter),
             not_enc_idx: Default::default(),
             root: Default::default(),
+            aux_vars: Default::default(),
             n_vars: Default::default(),
             n_clauses: Default::default(),
         }
@@ -351,6 +402,22 @@ impl Node {
         Node::Leaf { lit }
     }
 
+    /// Recursively collects the variables of all output literals of internal
+    /// nodes in the subtree
+    fn collect_aux_vars(&self, vars: &mut Vec<Var>) {
+        if let Node::Internal {
+            out_lits,
+            left,
+            right,
+            ..
+        } = self
+        {
+            left.collect_aux_vars(vars);
+            right.collect_aux_vars(vars);
+            vars.extend(out_lits.iter().filter_map(|ol| ol.map(|l| l.var())));
+        }
+    }
+
     /// Constructs a new internal node
     pub fn new_internal(left: Node, right: Node) -> Node {
         Node::Internal {
@@ -837,12 +904,31 @@ mod tests {
             card::{
                 BoundBoth, BoundLower, BoundLowerIncremental, BoundUpper, BoundUpperIncremental,
             },
-            EncodeStats, Error,
+            EncodeStats, Error, IterInputs,
         },
         instances::{BasicVarManager, Cnf, ManageVars},
         lit, var,
     };
 
+    #[test]
+    fn output_lits() {
+        let mut tot = Totalizer::from(vec![lit![0], lit![1], lit![2], lit![3]]);
+        let mut var_manager = BasicVarManager::default();
+        var_manager.increase_next_free(var![4]);
+        let mut cnf = Cnf::new();
+        assert_eq!(tot.output_lits(), Err(Error::NotEncoded));
+        tot.encode_ub(0..2, &mut cnf, &mut var_manager);
+        let outputs = tot.output_lits().unwrap();
+        assert_eq!(outputs.len(), 2);
+        assert_eq!(tot.enforce_ub(1).unwrap(), vec![!outputs[1]]);
+        tot.encode_ub(2..4, &mut cnf, &mut var_manager);
+        let all = tot.output_lits().unwrap();
+        assert_eq!(all.len(), 4);
+        assert_eq!(&all[..2], &outputs[..]);
+        tot.extend([lit![4]]);
+        assert_eq!(tot.output_lits(), Err(Error::NotEncoded));
+    }
+
     #[test]
     fn adder_1() {
         // Child nodes
@@ -1058,6 +1144,38 @@ mod tests {
         assert_eq!(cnf.len(), tot.n_clauses());
     }
 
+    #[test]
+    fn tot_narrow_range() {
+        let lits = vec![
+            lit![0],
+            lit![1],
+            lit![2],
+            lit![3],
+            lit![4],
+            lit![5],
+            lit![6],
+            lit![7],
+        ];
+        let mut full = Totalizer::from(lits.clone());
+        let mut var_manager = BasicVarManager::default();
+        var_manager.increase_next_free(var![8]);
+        let mut full_cnf = Cnf::new();
+        full.encode_both(.., &mut full_cnf, &mut var_manager);
+        let mut narrow = Totalizer::from(lits);
+        let mut var_manager = BasicVarManager::default();
+        var_manager.increase_next_free(var![8]);
+        let mut narrow_cnf = Cnf::new();
+        narrow.encode_both(3..=4, &mut narrow_cnf, &mut var_manager);
+        assert!(narrow_cnf.len() < full_cnf.len());
+        assert_eq!(narrow.n_clauses(), narrow_cnf.len());
+        for b in 3..=4 {
+            assert_eq!(narrow.enforce_ub(b).unwrap().len(), 1);
+            assert_eq!(narrow.enforce_lb(b).unwrap().len(), 1);
+        }
+        assert_eq!(narrow.enforce_ub(1), Err(Error::NotEncoded));
+        assert_eq!(narrow.enforce_lb(6), Err(Error::NotEncoded));
+    }
+
     #[test]
     fn tot_incremental_building_ub() {
         let mut tot1 = Totalizer::default();
@@ -1097,4 +1215,18 @@ mod tests {
         assert_eq!(cnf1.len(), tot1.n_clauses());
         assert_eq!(cnf2.len(), tot2.n_clauses());
     }
+
+    #[test]
+    fn aux_vars_disjoint_from_inputs() {
+        let mut tot = Totalizer::default();
+        tot.extend(vec![lit![0], lit![1], lit![2], lit![3]]);
+        assert!(tot.aux_vars().is_empty());
+        let mut var_manager = BasicVarManager::default();
+        var_manager.increase_next_free(var![4]);
+        let mut cnf = Cnf::new();
+        tot.encode_both(1..3, &mut cnf, &mut var_manager);
+        assert_eq!(tot.aux_vars().len(), tot.n_vars() as usize);
+        assert!(tot.aux_vars().iter().all(|&v| v >= var![4]));
+        assert!(tot.iter().all(|l| !tot.aux_vars().contains(&l.var())));
+    }
 }
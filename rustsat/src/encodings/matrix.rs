@@ -0,0 +1,84 @@
+//! # Encodings for Boolean Matrices
+//!
+//! Helpers for common modelling patterns over matrices of Boolean variables.
+//!
+//! ## Example Useage
+//!
+//! ```
+//! # use rustsat::{encodings::matrix, instances::BasicVarManager};
+//! #
+//! let mut var_manager = BasicVarManager::default();
+//! // Assign each of 3 tasks to exactly one of 4 workers
+//! let (grid, encoding) = matrix::assignment(3, 4, &mut var_manager);
+//! assert_eq!(grid.len(), 3);
+//! assert_eq!(grid[0].len(), 4);
+//! ```
+
+use super::am1::{self, Encode};
+use crate::{
+    instances::{Cnf, ManageVars},
+    types::{Clause, Lit},
+};
+
+/// Encodes an assignment problem over a `rows` by `cols` matrix of fresh
+/// variables. Each row is assigned to exactly one column and each column has
+/// at most one row assigned to it.
+///
+/// Returns the grid of variables (indexed as `grid[row][col]`) and the CNF
+/// encoding.
+pub fn assignment(
+    rows: usize,
+    cols: usize,
+    var_manager: &mut dyn ManageVars,
+) -> (Vec<Vec<Lit>>, Cnf) {
+    let grid: Vec<Vec<Lit>> = (0..rows)
+        .map(|_| (0..cols).map(|_| var_manager.new_lit()).collect())
+        .collect();
+    let mut encoding = Cnf::new();
+    for row in &grid {
+        encoding.add_clause(row.iter().copied().collect::<Clause>());
+        am1::Def::from(row.clone())
+            .encode(&mut encoding, var_manager)
+            .expect("at-most-1 encoding should never fail");
+    }
+    for col in 0..cols {
+        am1::Def::from_iter(grid.iter().map(|row| row[col]))
+            .encode(&mut encoding, var_manager)
+            .expect("at-most-1 encoding should never fail");
+    }
+    (grid, encoding)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::assignment;
+    use crate::{
+        instances::{BasicVarManager, ManageVars},
+        test_utils::all_assignments,
+    };
+
+    #[test]
+    fn assignment_3x3_permutations() {
+        let mut var_manager = BasicVarManager::default();
+        let (grid, encoding) = assignment(3, 3, &mut var_manager);
+        assert_eq!(grid.len(), 3);
+        assert!(grid.iter().all(|row| row.len() == 3));
+        let n_vars = var_manager.n_used();
+        assert_eq!(n_vars, 9);
+        let n_sols = all_assignments(n_vars)
+            .filter(|assign| encoding.is_satisfied(assign))
+            .count();
+        assert_eq!(n_sols, 6);
+    }
+
+    #[test]
+    fn assignment_more_rows_than_cols() {
+        let mut var_manager = BasicVarManager::default();
+        let (_, encoding) = assignment(3, 2, &mut var_manager);
+        let n_vars = var_manager.n_used();
+        let n_sols = all_assignments(n_vars)
+            .filter(|assign| encoding.is_satisfied(assign))
+            .count();
+        assert_eq!(n_sols, 0);
+    }
+}
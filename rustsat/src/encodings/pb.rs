@@ -40,10 +40,10 @@ use std::{
 use super::{card, CollectClauses, Error};
 use crate::{
     clause,
-    instances::ManageVars,
+    instances::{Cnf, ManageVars},
     types::{
         constraints::{PBConstraint, PBEQConstr, PBLBConstr, PBUBConstr},
-        Clause, Lit,
+        Clause, Lit, RsHashMap,
     },
 };
 
@@ -61,6 +61,18 @@ pub use dpw::DynamicPolyWatchdog;
 pub mod dbgte;
 pub use dbgte::DbGte;
 
+pub mod sorter;
+pub use sorter::SorterPb;
+
+pub mod bdd;
+pub use bdd::BddPb;
+
+pub mod adder;
+pub use adder::AdderNetwork;
+
+pub mod pw;
+pub use pw::PolynomialWatchdog;
+
 /// Trait for all pseudo-boolean encodings of form `weighted sum of lits <> rhs`
 pub trait Encode {
     /// Get the sum of weights in the encoding
@@ -383,6 +395,28 @@ pub fn default_encode_pb_constraint<Col: CollectClauses>(
     encode_pb_constraint::<DefBothBounding, Col>(constr, collector, var_manager)
 }
 
+/// Encodes a cumulative resource constraint, i.e., the sum of the resource
+/// demands of all active tasks must not exceed `capacity`. Each task is given
+/// as a literal that is true if the task is active and its demand. The
+/// weighted upper bound is encoded with a [`GeneralizedTotalizer`].
+pub fn cumulative(
+    tasks: &[(Lit, usize)],
+    capacity: usize,
+    var_manager: &mut dyn ManageVars,
+) -> Cnf {
+    let mut encoding = Cnf::new();
+    let mut enc = GeneralizedTotalizer::default();
+    enc.extend(tasks.iter().copied());
+    enc.encode_ub(capacity..=capacity, &mut encoding, var_manager);
+    encoding.extend(
+        enc.enforce_ub(capacity)
+            .unwrap()
+            .into_iter()
+            .map(|unit| clause![unit]),
+    );
+    encoding
+}
+
 /// An encoder for any pseudo-boolean constraint with an encoding of choice
 pub fn encode_pb_constraint<PBE: BoundBoth + FromIterator<(Lit, usize)>, Col: CollectClauses>(
     constr: PBConstraint,
@@ -415,6 +449,17 @@ pub fn encode_pb_constraint<PBE: BoundBoth + FromIterator<(Lit, usize)>, Col: Co
     PBE::encode_constr(constr, collector, var_manager).unwrap()
 }
 
+/// Checks whether all weights are equal, in which case the weighted inputs
+/// describe a cardinality constraint and a (cheaper) cardinality encoding can
+/// be used after dividing the bound by the weight.
+pub fn pb_is_cardinality(weights: &RsHashMap<Lit, usize>) -> bool {
+    let mut iter = weights.values();
+    match iter.next() {
+        Some(first) => iter.all(|w| w == first),
+        None => true,
+    }
+}
+
 fn prepare_ub_range<Enc: Encode, R: RangeBounds<usize>>(enc: &Enc, range: R) -> Range<usize> {
     (match range.start_bound() {
         Bound::Included(b) => *b,
@@ -0,0 +1,288 @@
+//! # Adder Network Pseudo-Boolean Encoding
+//!
+//! Implementation of the adder network pseudo-boolean encoding \[1\]. The
+//! coefficients are represented in binary and the weighted sum is computed
+//! with a network of full and half adders. The binary representation of the
+//! sum is then compared to the bound. The encoding is linear in the number of
+//! input literals times the number of bits of the coefficients.
+//!
+//! ## References
+//!
+//! - \[1\] Niklas Eén and Niklas Sörensson: _Translating Pseudo-Boolean
+//!   Constraints into SAT_, JSAT 2006.
+
+use super::{BoundUpper, Encode, Error};
+use crate::{
+    clause,
+    encodings::{CollectClauses, EncodeStats, IterWeightedInputs},
+    instances::ManageVars,
+    types::{Clause, Lit, RsHashMap},
+};
+use std::{
+    collections::{BTreeMap, VecDeque},
+    ops::RangeBounds,
+};
+
+/// Implementation of the adder network pseudo-boolean encoding \[1\]. This
+/// encoding only supports upper bounding. Extending the encoding after it has
+/// been built discards the network and rebuilds it on the next call to
+/// [`BoundUpper::encode_ub`].
+///
+/// # References
+///
+/// - \[1\] Niklas Eén and Niklas Sörensson: _Translating Pseudo-Boolean
+///   Constraints into SAT_, JSAT 2006.
+#[derive(Default)]
+pub struct AdderNetwork {
+    /// Input literals and weights for the encoding
+    in_lits: RsHashMap<Lit, usize>,
+    /// Sum of all input weight
+    weight_sum: usize,
+    /// The bits of the weighted sum, least significant first, if built. A
+    /// `None` literal represents the constant `false`.
+    sum_bits: Option<Vec<Option<Lit>>>,
+    /// Literals implied by the weighted sum exceeding an upper bound
+    ub_lits: BTreeMap<usize, Lit>,
+    /// The number of variables in the encoding
+    n_vars: u32,
+    /// The number of clauses in the encoding
+    n_clauses: usize,
+}
+
+impl AdderNetwork {
+    /// Builds the adder network and returns the bits of the weighted sum
+    fn build_network<Col>(
+        &self,
+        collector: &mut Col,
+        var_manager: &mut dyn ManageVars,
+    ) -> Vec<Option<Lit>>
+    where
+        Col: CollectClauses,
+    {
+        let mut columns: Vec<VecDeque<Lit>> = vec![];
+        for (&lit, &weight) in &self.in_lits {
+            let n_bits = (usize::BITS - weight.leading_zeros()) as usize;
+            if columns.len() < n_bits {
+                columns.resize_with(n_bits, Default::default);
+            }
+            for (bit, column) in columns.iter_mut().enumerate().take(n_bits) {
+                if (weight >> bit) & 1 == 1 {
+                    column.push_back(lit);
+                }
+            }
+        }
+        let mut sum_bits = Vec::with_capacity(columns.len());
+        let mut bit = 0;
+        while bit < columns.len() {
+            let mut carries = vec![];
+            let column = &mut columns[bit];
+            while column.len() >= 3 {
+                let a = column.pop_front().unwrap();
+                let b = column.pop_front().unwrap();
+                let c = column.pop_front().unwrap();
+                let (sum, carry) = full_adder(a, b, c, collector, var_manager);
+                column.push_back(sum);
+                carries.push(carry);
+            }
+            if column.len() == 2 {
+                let a = column.pop_front().unwrap();
+                let b = column.pop_front().unwrap();
+                let (sum, carry) = half_adder(a, b, collector, var_manager);
+                column.push_back(sum);
+                carries.push(carry);
+            }
+            sum_bits.push(column.pop_front());
+            if !carries.is_empty() {
+                if bit + 1 == columns.len() {
+                    columns.push(VecDeque::default());
+                }
+                columns[bit + 1].extend(carries);
+            }
+            bit += 1;
+        }
+        sum_bits
+    }
+
+    /// Encodes a literal that is implied if the weighted sum exceeds `ub`
+    fn encode_ub_lit<Col>(
+        sum_bits: &[Option<Lit>],
+        ub: usize,
+        collector: &mut Col,
+        var_manager: &mut dyn ManageVars,
+    ) -> Lit
+    where
+        Col: CollectClauses,
+    {
+        let olit = var_manager.new_lit();
+        // The sum is larger if it is equal to `ub` on all more significant
+        // bits and the first differing bit is set in the sum
+        let mut prefix = vec![];
+        for idx in (0..sum_bits.len()).rev() {
+            let bit = sum_bits[idx];
+            if (ub >> idx) & 1 == 1 {
+                match bit {
+                    Some(b) => prefix.push(!b),
+                    None => break,
+                }
+            } else if let Some(b) = bit {
+                let mut cl: Clause = prefix.iter().copied().collect();
+                cl.add(!b);
+                cl.add(olit);
+                collector.extend([cl]);
+            }
+        }
+        olit
+    }
+}
+
+/// Encodes a full adder and returns the sum and carry output. The outputs are
+/// implied by the inputs, which is sufficient for upper bounding.
+fn full_adder<Col>(
+    a: Lit,
+    b: Lit,
+    c: Lit,
+    collector: &mut Col,
+    var_manager: &mut dyn ManageVars,
+) -> (Lit, Lit)
+where
+    Col: CollectClauses,
+{
+    let sum = var_manager.new_lit();
+    let carry = var_manager.new_lit();
+    collector.extend([
+        // Carry is the majority of the inputs
+        clause![!a, !b, carry],
+        clause![!a, !c, carry],
+        clause![!b, !c, carry],
+        // Sum is the parity of the inputs
+        clause![!a, b, c, sum],
+        clause![a, !b, c, sum],
+        clause![a, b, !c, sum],
+        clause![!a, !b, !c, sum],
+    ]);
+    (sum, carry)
+}
+
+/// Encodes a half adder and returns the sum and carry output. The outputs are
+/// implied by the inputs, which is sufficient for upper bounding.
+fn half_adder<Col>(
+    a: Lit,
+    b: Lit,
+    collector: &mut Col,
+    var_manager: &mut dyn ManageVars,
+) -> (Lit, Lit)
+where
+    Col: CollectClauses,
+{
+    let sum = var_manager.new_lit();
+    let carry = var_manager.new_lit();
+    collector.extend([
+        clause![!a, !b, carry],
+        clause![!a, b, sum],
+        clause![a, !b, sum],
+    ]);
+    (sum, carry)
+}
+
+impl Encode for AdderNetwork {
+    fn weight_sum(&self) -> usize {
+        self.weight_sum
+    }
+}
+
+impl IterWeightedInputs for AdderNetwork {
+    type Iter<'a> = super::gte::GteIter<'a>;
+
+    fn iter(&self) -> Self::Iter<'_> {
+        self.in_lits.iter().map(super::gte::copy_key_val)
+    }
+}
+
+impl BoundUpper for AdderNetwork {
+    fn encode_ub<Col, R>(&mut self, range: R, collector: &mut Col, var_manager: &mut dyn ManageVars)
+    where
+        Col: CollectClauses,
+        R: RangeBounds<usize>,
+    {
+        let range = super::prepare_ub_range(self, range);
+        if range.is_empty() {
+            return;
+        };
+        let n_vars_before = var_manager.n_used();
+        let n_clauses_before = collector.n_clauses();
+        if self.sum_bits.is_none() {
+            self.sum_bits = Some(self.build_network(collector, var_manager));
+        }
+        let sum_bits = self.sum_bits.as_ref().unwrap();
+        for ub in range {
+            self.ub_lits.entry(ub).or_insert_with(|| {
+                AdderNetwork::encode_ub_lit(sum_bits, ub, collector, var_manager)
+            });
+        }
+        self.n_clauses += collector.n_clauses() - n_clauses_before;
+        self.n_vars += var_manager.n_used() - n_vars_before;
+    }
+
+    fn enforce_ub(&self, ub: usize) -> Result<Vec<Lit>, Error> {
+        if ub >= self.weight_sum {
+            return Ok(vec![]);
+        }
+        match self.ub_lits.get(&ub) {
+            Some(&olit) => Ok(vec![!olit]),
+            None => Err(Error::NotEncoded),
+        }
+    }
+}
+
+impl EncodeStats for AdderNetwork {
+    fn n_clauses(&self) -> usize {
+        self.n_clauses
+    }
+
+    fn n_vars(&self) -> u32 {
+        self.n_vars
+    }
+}
+
+impl From<RsHashMap<Lit, usize>> for AdderNetwork {
+    fn from(mut lits: RsHashMap<Lit, usize>) -> Self {
+        lits.retain(|_, w| *w > 0);
+        let weight_sum = lits.iter().fold(0, |sum, (_, w)| sum + *w);
+        Self {
+            in_lits: lits,
+            weight_sum,
+            ..Default::default()
+        }
+    }
+}
+
+impl FromIterator<(Lit, usize)> for AdderNetwork {
+    fn from_iter<T: IntoIterator<Item = (Lit, usize)>>(iter: T) -> Self {
+        let mut enc = Self::default();
+        enc.extend(iter);
+        enc
+    }
+}
+
+impl Extend<(Lit, usize)> for AdderNetwork {
+    /// Adds weighted input literals to the encoding. The weights of literals
+    /// that are added multiple times are summed up, also if the literal is
+    /// already in the encoding. Literals with weight zero are ignored.
+    fn extend<T: IntoIterator<Item = (Lit, usize)>>(&mut self, iter: T) {
+        iter.into_iter().for_each(|(l, w)| {
+            if w == 0 {
+                return;
+            }
+            self.weight_sum += w;
+            match self.in_lits.get_mut(&l) {
+                Some(old_w) => *old_w += w,
+                None => {
+                    self.in_lits.insert(l, w);
+                }
+            };
+        });
+        // The network needs to be rebuilt with the new inputs
+        self.sum_bits = None;
+        self.ub_lits.clear();
+    }
+}
@@ -0,0 +1,228 @@
+//! # Binary Decision Diagram Based Pseudo-Boolean Encoding
+//!
+//! Implementation of the BDD-based pseudo-boolean encoding \[1\]. The encoding
+//! builds an ordered BDD over the weighted sum and translates every node into
+//! clauses. Since the size of the BDD depends on the number of distinct
+//! partial sums rather than on the size of the coefficients, this encoding can
+//! be much smaller than the generalized totalizer for constraints with large
+//! coefficients.
+//!
+//! ## References
+//!
+//! - \[1\] Niklas Eén and Niklas Sörensson: _Translating Pseudo-Boolean
+//!   Constraints into SAT_, JSAT 2006.
+
+use super::{BoundUpper, Encode, Error};
+use crate::{
+    clause,
+    encodings::{CollectClauses, EncodeStats, IterWeightedInputs},
+    instances::ManageVars,
+    types::{Lit, RsHashMap},
+};
+use std::ops::RangeBounds;
+
+/// Implementation of the BDD-based pseudo-boolean encoding \[1\]. The input
+/// literals are ordered by descending weight. Node `(idx, slack)` of the BDD
+/// is implied if the weighted sum of the input literals from `idx` onwards
+/// exceeds `slack`, where `slack` is the bound minus the weight accumulated by
+/// the literals before `idx`. Nodes are shared via a table keyed on
+/// `(idx, slack)`, which also shares nodes between different bounds. The BDD
+/// is not reduced: nodes for different slacks at the same `idx` that
+/// represent the same function are not merged. This encoding only supports
+/// upper bounding.
+///
+/// The BDD is built recursively, so the recursion depth is bounded by the
+/// number of input literals. Extending the encoding after it has
+/// been built discards the BDD and rebuilds it on the next call to
+/// [`BoundUpper::encode_ub`].
+///
+/// # References
+///
+/// - \[1\] Niklas Eén and Niklas Sörensson: _Translating Pseudo-Boolean
+///   Constraints into SAT_, JSAT 2006.
+#[derive(Default)]
+pub struct BddPb {
+    /// Input literals and weights for the encoding
+    in_lits: RsHashMap<Lit, usize>,
+    /// Sum of all input weight
+    weight_sum: usize,
+    /// The input literals ordered by descending weight, if the BDD is built
+    ordered: Vec<(Lit, usize)>,
+    /// The sum of the weights from each position in `ordered` onwards
+    suffix_sums: Vec<usize>,
+    /// The encoded BDD nodes, keyed on `(idx, slack)`
+    nodes: RsHashMap<(usize, usize), Lit>,
+    /// The number of variables in the encoding
+    n_vars: u32,
+    /// The number of clauses in the encoding
+    n_clauses: usize,
+}
+
+/// A node in the BDD
+#[derive(Clone, Copy)]
+enum Node {
+    /// A constant terminal node
+    Const(bool),
+    /// An internal node represented by a literal
+    Lit(Lit),
+}
+
+impl BddPb {
+    /// Orders the input literals for building the BDD
+    fn order_inputs(&mut self) {
+        self.ordered = self
+            .in_lits
+            .iter()
+            .filter(|(_, &w)| w > 0)
+            .map(|(&l, &w)| (l, w))
+            .collect();
+        self.ordered
+            .sort_unstable_by(|(l1, w1), (l2, w2)| w2.cmp(w1).then(l1.cmp(l2)));
+        self.suffix_sums = vec![0; self.ordered.len() + 1];
+        for idx in (0..self.ordered.len()).rev() {
+            self.suffix_sums[idx] = self.suffix_sums[idx + 1] + self.ordered[idx].1;
+        }
+    }
+
+    /// Recursively encodes the node that is implied if the weighted sum of the
+    /// literals from `idx` onwards exceeds `slack`. Every level of recursion
+    /// moves to the next input literal.
+    fn encode_node<Col>(
+        &mut self,
+        idx: usize,
+        slack: usize,
+        collector: &mut Col,
+        var_manager: &mut dyn ManageVars,
+    ) -> Node
+    where
+        Col: CollectClauses,
+    {
+        if slack >= self.suffix_sums[idx] {
+            return Node::Const(false);
+        }
+        if let Some(&olit) = self.nodes.get(&(idx, slack)) {
+            return Node::Lit(olit);
+        }
+        let (lit, weight) = self.ordered[idx];
+        let hi = if weight > slack {
+            Node::Const(true)
+        } else {
+            self.encode_node(idx + 1, slack - weight, collector, var_manager)
+        };
+        let lo = self.encode_node(idx + 1, slack, collector, var_manager);
+        let olit = var_manager.new_lit();
+        match hi {
+            Node::Const(true) => collector.extend([clause![!lit, olit]]),
+            Node::Lit(hi) => collector.extend([clause![!lit, !hi, olit]]),
+            Node::Const(false) => (),
+        }
+        match lo {
+            Node::Const(true) => collector.extend([clause![olit]]),
+            Node::Lit(lo) => collector.extend([clause![!lo, olit]]),
+            Node::Const(false) => (),
+        }
+        self.nodes.insert((idx, slack), olit);
+        Node::Lit(olit)
+    }
+}
+
+impl Encode for BddPb {
+    fn weight_sum(&self) -> usize {
+        self.weight_sum
+    }
+}
+
+impl IterWeightedInputs for BddPb {
+    type Iter<'a> = super::gte::GteIter<'a>;
+
+    fn iter(&self) -> Self::Iter<'_> {
+        self.in_lits.iter().map(super::gte::copy_key_val)
+    }
+}
+
+impl BoundUpper for BddPb {
+    fn encode_ub<Col, R>(&mut self, range: R, collector: &mut Col, var_manager: &mut dyn ManageVars)
+    where
+        Col: CollectClauses,
+        R: RangeBounds<usize>,
+    {
+        let range = super::prepare_ub_range(self, range);
+        if range.is_empty() {
+            return;
+        };
+        let n_vars_before = var_manager.n_used();
+        let n_clauses_before = collector.n_clauses();
+        if self.suffix_sums.is_empty() {
+            self.order_inputs();
+        }
+        for ub in range {
+            self.encode_node(0, ub, collector, var_manager);
+        }
+        self.n_clauses += collector.n_clauses() - n_clauses_before;
+        self.n_vars += var_manager.n_used() - n_vars_before;
+    }
+
+    fn enforce_ub(&self, ub: usize) -> Result<Vec<Lit>, Error> {
+        if ub >= self.weight_sum {
+            return Ok(vec![]);
+        }
+        match self.nodes.get(&(0, ub)) {
+            Some(&olit) => Ok(vec![!olit]),
+            None => Err(Error::NotEncoded),
+        }
+    }
+}
+
+impl EncodeStats for BddPb {
+    fn n_clauses(&self) -> usize {
+        self.n_clauses
+    }
+
+    fn n_vars(&self) -> u32 {
+        self.n_vars
+    }
+}
+
+impl From<RsHashMap<Lit, usize>> for BddPb {
+    fn from(mut lits: RsHashMap<Lit, usize>) -> Self {
+        lits.retain(|_, w| *w > 0);
+        let weight_sum = lits.iter().fold(0, |sum, (_, w)| sum + *w);
+        Self {
+            in_lits: lits,
+            weight_sum,
+            ..Default::default()
+        }
+    }
+}
+
+impl FromIterator<(Lit, usize)> for BddPb {
+    fn from_iter<T: IntoIterator<Item = (Lit, usize)>>(iter: T) -> Self {
+        let mut enc = Self::default();
+        enc.extend(iter);
+        enc
+    }
+}
+
+impl Extend<(Lit, usize)> for BddPb {
+    /// Adds weighted input literals to the encoding. The weights of literals
+    /// that are added multiple times are summed up, also if the literal is
+    /// already in the encoding. Literals with weight zero are ignored.
+    fn extend<T: IntoIterator<Item = (Lit, usize)>>(&mut self, iter: T) {
+        iter.into_iter().for_each(|(l, w)| {
+            if w == 0 {
+                return;
+            }
+            self.weight_sum += w;
+            match self.in_lits.get_mut(&l) {
+                Some(old_w) => *old_w += w,
+                None => {
+                    self.in_lits.insert(l, w);
+                }
+            };
+        });
+        // The BDD needs to be rebuilt with the new inputs
+        self.ordered.clear();
+        self.suffix_sums.clear();
+        self.nodes.clear();
+    }
+}
@@ -264,7 +264,8 @@ impl EncodeStats for DbGte {
 }
 
 impl From<RsHashMap<Lit, usize>> for DbGte {
-    fn from(lits: RsHashMap<Lit, usize>) -> Self {
+    fn from(mut lits: RsHashMap<Lit, usize>) -> Self {
+        lits.retain(|_, w| *w > 0);
         Self {
             lit_buffer: lits,
             ..Default::default()
@@ -274,14 +275,21 @@ impl From<RsHashMap<Lit, usize>> for DbGte {
 
 impl FromIterator<(Lit, usize)> for DbGte {
     fn from_iter<T: IntoIterator<Item = (Lit, usize)>>(iter: T) -> Self {
-        let lits: RsHashMap<Lit, usize> = RsHashMap::from_iter(iter);
-        Self::from(lits)
+        let mut dbgte = Self::default();
+        dbgte.extend(iter);
+        dbgte
     }
 }
 
 impl Extend<(Lit, usize)> for DbGte {
+    /// Adds weighted input literals to the encoding. The weights of literals
+    /// that are added multiple times are summed up, also if the literal is
+    /// already in the encoding. Literals with weight zero are ignored.
     fn extend<T: IntoIterator<Item = (Lit, usize)>>(&mut self, iter: T) {
         iter.into_iter().for_each(|(l, w)| {
+            if w == 0 {
+                return;
+            }
             // Insert into buffer to be added to tree
             match self.lit_buffer.get_mut(&l) {
                 Some(old_w) => *old_w += w,
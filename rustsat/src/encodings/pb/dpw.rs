@@ -196,7 +196,8 @@ impl EncodeStats for DynamicPolyWatchdog {
 }
 
 impl From<RsHashMap<Lit, usize>> for DynamicPolyWatchdog {
-    fn from(lits: RsHashMap<Lit, usize>) -> Self {
+    fn from(mut lits: RsHashMap<Lit, usize>) -> Self {
+        lits.retain(|_, w| *w > 0);
         let weight_sum = lits.iter().fold(0, |sum, (_, w)| sum + *w);
         Self {
             in_lits: lits.clone(),
@@ -210,8 +211,13 @@ impl From<RsHashMap<Lit, usize>> for DynamicPolyWatchdog {
 }
 
 impl FromIterator<(Lit, usize)> for DynamicPolyWatchdog {
+    /// Builds the encoding from weighted input literals. The weights of
+    /// literals that appear multiple times are summed up. Literals with weight
+    /// zero are ignored.
     fn from_iter<T: IntoIterator<Item = (Lit, usize)>>(iter: T) -> Self {
-        let lits: RsHashMap<Lit, usize> = RsHashMap::from_iter(iter);
+        let mut lits: RsHashMap<Lit, usize> = RsHashMap::default();
+        iter.into_iter()
+            .for_each(|(l, w)| *lits.entry(l).or_insert(0) += w);
         Self::from(lits)
     }
 }
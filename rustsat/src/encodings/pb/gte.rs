@@ -26,6 +26,9 @@ use std::{
 /// negating the input literals. This is implemented in
 /// [`super::simulators::Inverted`].
 ///
+/// Adding a literal that is already an input accumulates its weights, adding
+/// a literal with weight zero has no effect.
+///
 /// # References
 ///
 /// - \[1\] Saurabh Joshi and Ruben Martins and Vasco Manquinho: _Generalized
@@ -278,7 +281,8 @@ pub(super) type GteIter<'a> = std::iter::Map<
 >;
 
 impl From<RsHashMap<Lit, usize>> for GeneralizedTotalizer {
-    fn from(lits: RsHashMap<Lit, usize>) -> Self {
+    fn from(mut lits: RsHashMap<Lit, usize>) -> Self {
+        lits.retain(|_, w| *w > 0);
         let weight_sum = lits.iter().fold(0, |sum, (_, w)| sum + *w);
         Self {
             in_lits: lits.clone(),
@@ -291,14 +295,21 @@ impl From<RsHashMap<Lit, usize>> for GeneralizedTotalizer {
 
 impl FromIterator<(Lit, usize)> for GeneralizedTotalizer {
     fn from_iter<T: IntoIterator<Item = (Lit, usize)>>(iter: T) -> Self {
-        let lits: RsHashMap<Lit, usize> = RsHashMap::from_iter(iter);
-        Self::from(lits)
+        let mut gte = Self::default();
+        gte.extend(iter);
+        gte
     }
 }
 
 impl Extend<(Lit, usize)> for GeneralizedTotalizer {
+    /// Adds weighted input literals to the encoding. The weights of literals
+    /// that are added multiple times are summed up, also if the literal is
+    /// already in the encoding. Literals with weight zero are ignored.
     fn extend<T: IntoIterator<Item = (Lit, usize)>>(&mut self, iter: T) {
         iter.into_iter().for_each(|(l, w)| {
+            if w == 0 {
+                return;
+            }
             self.weight_sum += w;
             // Insert into buffer to be added to tree
             match self.lit_buffer.get_mut(&l) {
@@ -696,8 +707,8 @@ mod tests {
     use crate::{
         encodings::{
             card,
-            pb::{BoundUpper, BoundUpperIncremental},
-            EncodeStats, Error,
+            pb::{BoundUpper, BoundUpperIncremental, Encode},
+            EncodeStats, Error, IterWeightedInputs,
         },
         instances::{BasicVarManager, Cnf, ManageVars},
         lit,
@@ -882,6 +893,33 @@ mod tests {
         assert_eq!(cnf2.len(), gte2.n_clauses());
     }
 
+    #[test]
+    fn ub_gte_accumulate_weights() {
+        let mut gte = GeneralizedTotalizer::default();
+        gte.extend([(lit![0], 2)]);
+        let mut var_manager = BasicVarManager::default();
+        var_manager.increase_next_free(var![2]);
+        let mut cnf = Cnf::new();
+        gte.encode_ub(0..5, &mut cnf, &mut var_manager);
+        gte.extend([(lit![0], 3), (lit![1], 0)]);
+        assert_eq!(gte.weight_sum(), 5);
+        assert_eq!(gte.iter().collect::<Vec<_>>(), vec![(lit![0], 5)]);
+        gte.encode_ub_change(0..5, &mut cnf, &mut var_manager);
+        for ub in 0..5 {
+            let mut cnf = cnf.clone();
+            cnf.add_unit(lit![0]);
+            for unit in gte.enforce_ub(ub).unwrap() {
+                cnf.add_unit(unit);
+            }
+            let (_, conflict) = cnf.propagate_units_with_conflict();
+            assert!(conflict);
+        }
+        assert_eq!(gte.enforce_ub(5).unwrap(), vec![]);
+        let gte = GeneralizedTotalizer::from_iter([(lit![0], 2), (lit![0], 3), (lit![1], 0)]);
+        assert_eq!(gte.weight_sum(), 5);
+        assert_eq!(gte.iter().collect::<Vec<_>>(), vec![(lit![0], 5)]);
+    }
+
     #[test]
     fn ub_gte_multiplication() {
         let mut gte1 = GeneralizedTotalizer::default();
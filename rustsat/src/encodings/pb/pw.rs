@@ -0,0 +1,216 @@
+//! # Polynomial Watchdog Encoding
+//!
+//! Implementation of the (static) polynomial watchdog encoding \[1\]. The
+//! weights are decomposed into their binary representation and the input
+//! literals are put into one bucket per bit. Each bucket is sorted with a
+//! totalizer and every second output of a bucket is carried to the next
+//! higher bucket. The bound is shifted by a constant tare such that violating
+//! it corresponds to a single output of the highest bucket, the watchdog.
+//! Since the buckets only grow with the number of inputs and not with the
+//! size of the weights, coefficients spanning many orders of magnitude do not
+//! lead to a single large totalizer.
+//!
+//! For the incremental variant of this encoding, see
+//! [`crate::encodings::pb::DynamicPolyWatchdog`].
+//!
+//! ## References
+//!
+//! - \[1\] Olivier Bailleux and Yacine Boufkhad and Olivier Roussel: _New
+//!   Encodings of Pseudo-Boolean Constraints into CNF_, SAT 2009.
+
+use super::{BoundUpper, Encode, Error};
+use crate::{
+    clause,
+    encodings::{
+        card::{self, Totalizer},
+        CollectClauses, EncodeStats, IterWeightedInputs,
+    },
+    instances::ManageVars,
+    types::{Lit, RsHashMap},
+};
+use std::ops::RangeBounds;
+
+/// Implementation of the (static) polynomial watchdog encoding \[1\]. A
+/// separate watchdog is built for every encoded upper bound, since the tare
+/// depends on the bound. This encoding only supports upper bounding.
+/// Extending the encoding after it has been built discards all watchdogs and
+/// rebuilds them on the next call to [`BoundUpper::encode_ub`].
+///
+/// # References
+///
+/// - \[1\] Olivier Bailleux and Yacine Boufkhad and Olivier Roussel: _New
+///   Encodings of Pseudo-Boolean Constraints into CNF_, SAT 2009.
+#[derive(Default)]
+pub struct PolynomialWatchdog {
+    /// Input literals and weights for the encoding
+    in_lits: RsHashMap<Lit, usize>,
+    /// Sum of all input weight
+    weight_sum: usize,
+    /// The watchdog for each encoded bound. `None` if the bound can not be
+    /// violated by the tared sum.
+    watchdogs: RsHashMap<usize, Option<Lit>>,
+    /// A literal fixed to true, used as input for the tare
+    true_lit: Option<Lit>,
+    /// The number of variables in the encoding
+    n_vars: u32,
+    /// The number of clauses in the encoding
+    n_clauses: usize,
+}
+
+impl PolynomialWatchdog {
+    /// Builds the watchdog for an upper bound `ub < weight_sum`
+    fn encode_watchdog<Col>(
+        &mut self,
+        ub: usize,
+        collector: &mut Col,
+        var_manager: &mut dyn ManageVars,
+    ) -> Option<Lit>
+    where
+        Col: CollectClauses,
+    {
+        let max_weight = self.in_lits.values().copied().max().unwrap_or(0);
+        debug_assert!(max_weight > 0);
+        let top_bit = max_weight.ilog2() as usize;
+        let top_weight = 1usize << top_bit;
+        // Tare such that `ub + 1 + tare` is a multiple of the top weight
+        let tare = (top_weight - (ub + 1) % top_weight) % top_weight;
+        let n_top = (ub + 1 + tare) / top_weight;
+        let true_lit = if tare > 0 {
+            Some(*self.true_lit.get_or_insert_with(|| {
+                let lit = var_manager.new_lit();
+                collector.extend([clause![lit]]);
+                lit
+            }))
+        } else {
+            None
+        };
+        let mut carries: Vec<Lit> = vec![];
+        for bit in 0..=top_bit {
+            // Outputs needed from this bucket to eventually reach `n_top`
+            let n_needed = n_top.saturating_mul(1 << (top_bit - bit));
+            let mut bucket = carries;
+            bucket.extend(
+                self.in_lits
+                    .iter()
+                    .filter(|(_, &w)| w & (1 << bit) != 0)
+                    .map(|(&l, _)| l),
+            );
+            if tare & (1 << bit) != 0 {
+                bucket.push(true_lit.unwrap());
+            }
+            if bucket.is_empty() {
+                carries = vec![];
+                continue;
+            }
+            let mut tot = Totalizer::from(bucket);
+            card::BoundUpper::encode_ub(&mut tot, 0..n_needed, collector, var_manager);
+            let outputs = tot.output_lits().unwrap();
+            if bit == top_bit {
+                return outputs.get(n_top - 1).copied();
+            }
+            carries = outputs.into_iter().skip(1).step_by(2).collect();
+        }
+        unreachable!()
+    }
+}
+
+impl Encode for PolynomialWatchdog {
+    fn weight_sum(&self) -> usize {
+        self.weight_sum
+    }
+}
+
+impl IterWeightedInputs for PolynomialWatchdog {
+    type Iter<'a> = super::gte::GteIter<'a>;
+
+    fn iter(&self) -> Self::Iter<'_> {
+        self.in_lits.iter().map(super::gte::copy_key_val)
+    }
+}
+
+impl BoundUpper for PolynomialWatchdog {
+    fn encode_ub<Col, R>(&mut self, range: R, collector: &mut Col, var_manager: &mut dyn ManageVars)
+    where
+        Col: CollectClauses,
+        R: RangeBounds<usize>,
+    {
+        let range = super::prepare_ub_range(self, range);
+        if range.is_empty() {
+            return;
+        };
+        let n_vars_before = var_manager.n_used();
+        let n_clauses_before = collector.n_clauses();
+        for ub in range {
+            if self.watchdogs.contains_key(&ub) {
+                continue;
+            }
+            let watchdog = self.encode_watchdog(ub, collector, var_manager);
+            self.watchdogs.insert(ub, watchdog);
+        }
+        self.n_clauses += collector.n_clauses() - n_clauses_before;
+        self.n_vars += var_manager.n_used() - n_vars_before;
+    }
+
+    fn enforce_ub(&self, ub: usize) -> Result<Vec<Lit>, Error> {
+        if ub >= self.weight_sum {
+            return Ok(vec![]);
+        }
+        match self.watchdogs.get(&ub) {
+            Some(Some(watchdog)) => Ok(vec![!*watchdog]),
+            Some(None) => Ok(vec![]),
+            None => Err(Error::NotEncoded),
+        }
+    }
+}
+
+impl EncodeStats for PolynomialWatchdog {
+    fn n_clauses(&self) -> usize {
+        self.n_clauses
+    }
+
+    fn n_vars(&self) -> u32 {
+        self.n_vars
+    }
+}
+
+impl From<RsHashMap<Lit, usize>> for PolynomialWatchdog {
+    fn from(mut lits: RsHashMap<Lit, usize>) -> Self {
+        lits.retain(|_, w| *w > 0);
+        let weight_sum = lits.iter().fold(0, |sum, (_, w)| sum + *w);
+        Self {
+            in_lits: lits,
+            weight_sum,
+            ..Default::default()
+        }
+    }
+}
+
+impl FromIterator<(Lit, usize)> for PolynomialWatchdog {
+    fn from_iter<T: IntoIterator<Item = (Lit, usize)>>(iter: T) -> Self {
+        let mut enc = Self::default();
+        enc.extend(iter);
+        enc
+    }
+}
+
+impl Extend<(Lit, usize)> for PolynomialWatchdog {
+    /// Adds weighted input literals to the encoding. The weights of literals
+    /// that are added multiple times are summed up, also if the literal is
+    /// already in the encoding. Literals with weight zero are ignored.
+    fn extend<T: IntoIterator<Item = (Lit, usize)>>(&mut self, iter: T) {
+        iter.into_iter().for_each(|(l, w)| {
+            if w == 0 {
+                return;
+            }
+            self.weight_sum += w;
+            match self.in_lits.get_mut(&l) {
+                Some(old_w) => *old_w += w,
+                None => {
+                    self.in_lits.insert(l, w);
+                }
+            };
+        });
+        // The watchdogs need to be rebuilt with the new inputs
+        self.watchdogs.clear();
+    }
+}
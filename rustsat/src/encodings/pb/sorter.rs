@@ -0,0 +1,376 @@
+//! # Sorting Network Based Pseudo-Boolean Encoding
+//!
+//! Implementation of the sorter-based pseudo-boolean encoding \[1\] as used in
+//! PBLib \[2\]. Coefficients are decomposed in base 2 and every digit is
+//! represented by a bucket that is sorted with an odd-even merge sorting
+//! network. Carries are propagated from one bucket to the next.
+//!
+//! ## References
+//!
+//! - \[1\] Niklas Eén and Niklas Sörensson: _Translating Pseudo-Boolean
+//!   Constraints into SAT_, JSAT 2006.
+//! - \[2\] Tobias Philipp and Peter Steinke: _PBLib - A Library for Encoding
+//!   Pseudo-Boolean Constraints into CNF_, SAT 2015.
+
+use super::{BoundUpper, Encode, Error};
+use crate::{
+    clause,
+    encodings::{CollectClauses, EncodeStats, IterWeightedInputs},
+    instances::ManageVars,
+    types::{Clause, Lit, RsHashMap},
+};
+use std::{collections::BTreeMap, ops::RangeBounds};
+
+/// Implementation of the sorter-based pseudo-boolean encoding \[1\]. The
+/// encoding builds one sorting network per binary digit of the coefficients and
+/// compares the resulting digits to the bound. This encoding only supports
+/// upper bounding. Lower bounding can be achieved by negating the input
+/// literals. Extending the encoding after it has been built discards the
+/// built network and rebuilds it on the next call to
+/// [`BoundUpper::encode_ub`].
+///
+/// # References
+///
+/// - \[1\] Niklas Eén and Niklas Sörensson: _Translating Pseudo-Boolean
+///   Constraints into SAT_, JSAT 2006.
+#[derive(Default)]
+pub struct SorterPb {
+    /// Input literals and weights for the encoding
+    in_lits: RsHashMap<Lit, usize>,
+    /// Sum of all input weight
+    weight_sum: usize,
+    /// The sorting networks, if built
+    network: Option<Network>,
+    /// Literals implied by the weighted sum exceeding an upper bound
+    ub_lits: BTreeMap<usize, Lit>,
+    /// The number of variables in the encoding
+    n_vars: u32,
+    /// The number of clauses in the encoding
+    n_clauses: usize,
+}
+
+/// The encoded digits of the weighted sum. A `None` literal represents the
+/// constant `false`.
+struct Network {
+    /// The sorted outputs of the most significant bucket
+    top: Vec<Option<Lit>>,
+    /// The parities of all less significant buckets
+    parities: Vec<Option<Lit>>,
+}
+
+impl SorterPb {
+    /// Builds the sorting networks for all buckets
+    fn build_network<Col>(&self, collector: &mut Col, var_manager: &mut dyn ManageVars) -> Network
+    where
+        Col: CollectClauses,
+    {
+        let max_weight = self
+            .in_lits
+            .values()
+            .fold(0, |max, &w| std::cmp::max(max, w));
+        let n_buckets = (usize::BITS - max_weight.leading_zeros()) as usize;
+        let mut parities = Vec::with_capacity(n_buckets.saturating_sub(1));
+        let mut carries: Vec<Option<Lit>> = vec![];
+        for bucket in 0..n_buckets {
+            let mut inputs: Vec<Option<Lit>> = self
+                .in_lits
+                .iter()
+                .filter_map(|(&l, &w)| {
+                    if (w >> bucket) & 1 == 1 {
+                        Some(Some(l))
+                    } else {
+                        None
+                    }
+                })
+                .collect();
+            inputs.append(&mut carries);
+            let outputs = sort(inputs, collector, var_manager);
+            if bucket + 1 == n_buckets {
+                return Network {
+                    top: outputs,
+                    parities,
+                };
+            }
+            parities.push(parity(&outputs, collector, var_manager));
+            carries = outputs.into_iter().skip(1).step_by(2).collect();
+        }
+        Network {
+            top: vec![],
+            parities,
+        }
+    }
+
+    /// Encodes a literal that is implied if the weighted sum exceeds `ub`
+    fn encode_ub_lit<Col>(
+        network: &Network,
+        ub: usize,
+        collector: &mut Col,
+        var_manager: &mut dyn ManageVars,
+    ) -> Lit
+    where
+        Col: CollectClauses,
+    {
+        let olit = var_manager.new_lit();
+        let top_idx = network.parities.len();
+        let ub_top = ub >> top_idx;
+        // Most significant digit strictly larger
+        if let Some(Some(l)) = network.top.get(ub_top) {
+            collector.extend([clause![!*l, olit]]);
+        }
+        // Most significant digit equal and first differing lower digit larger
+        let mut prefix = vec![];
+        if ub_top > 0 {
+            match network.top.get(ub_top - 1) {
+                Some(Some(l)) => prefix.push(!*l),
+                _ => return olit,
+            }
+        }
+        for idx in (0..top_idx).rev() {
+            let par = network.parities[idx];
+            if (ub >> idx) & 1 == 1 {
+                match par {
+                    Some(p) => prefix.push(!p),
+                    None => break,
+                }
+            } else if let Some(p) = par {
+                let mut cl: Clause = prefix.iter().copied().collect();
+                cl.add(!p);
+                cl.add(olit);
+                collector.extend([cl]);
+            }
+        }
+        olit
+    }
+}
+
+/// Sorts the input literals in descending order with an odd-even merge sorting
+/// network. The returned outputs are fully defined by the inputs.
+fn sort<Col>(
+    mut lits: Vec<Option<Lit>>,
+    collector: &mut Col,
+    var_manager: &mut dyn ManageVars,
+) -> Vec<Option<Lit>>
+where
+    Col: CollectClauses,
+{
+    if lits.len() <= 1 {
+        return lits;
+    }
+    let n_lits = lits.len();
+    lits.resize(n_lits.next_power_of_two(), None);
+    let mut sorted = sort_rec(&lits, collector, var_manager);
+    sorted.truncate(n_lits);
+    sorted
+}
+
+/// Recursively sorts a power-of-two sized sequence
+fn sort_rec<Col>(
+    lits: &[Option<Lit>],
+    collector: &mut Col,
+    var_manager: &mut dyn ManageVars,
+) -> Vec<Option<Lit>>
+where
+    Col: CollectClauses,
+{
+    if lits.len() == 1 {
+        return lits.to_vec();
+    }
+    let split = lits.len() / 2;
+    let left = sort_rec(&lits[..split], collector, var_manager);
+    let right = sort_rec(&lits[split..], collector, var_manager);
+    merge(&left, &right, collector, var_manager)
+}
+
+/// Merges two sorted power-of-two sized sequences of equal length
+fn merge<Col>(
+    left: &[Option<Lit>],
+    right: &[Option<Lit>],
+    collector: &mut Col,
+    var_manager: &mut dyn ManageVars,
+) -> Vec<Option<Lit>>
+where
+    Col: CollectClauses,
+{
+    debug_assert_eq!(left.len(), right.len());
+    if left.len() == 1 {
+        let (max, min) = compare(left[0], right[0], collector, var_manager);
+        return vec![max, min];
+    }
+    let evens = |lits: &[Option<Lit>]| lits.iter().copied().step_by(2).collect::<Vec<_>>();
+    let odds = |lits: &[Option<Lit>]| lits.iter().copied().skip(1).step_by(2).collect::<Vec<_>>();
+    let even = merge(&evens(left), &evens(right), collector, var_manager);
+    let odd = merge(&odds(left), &odds(right), collector, var_manager);
+    let mut merged = Vec::with_capacity(2 * left.len());
+    merged.push(even[0]);
+    for idx in 0..odd.len() - 1 {
+        let (max, min) = compare(odd[idx], even[idx + 1], collector, var_manager);
+        merged.push(max);
+        merged.push(min);
+    }
+    merged.push(odd[odd.len() - 1]);
+    merged
+}
+
+/// Encodes a comparator returning the maximum and the minimum of two literals
+fn compare<Col>(
+    a: Option<Lit>,
+    b: Option<Lit>,
+    collector: &mut Col,
+    var_manager: &mut dyn ManageVars,
+) -> (Option<Lit>, Option<Lit>)
+where
+    Col: CollectClauses,
+{
+    match (a, b) {
+        (None, x) | (x, None) => (x, None),
+        (Some(a), Some(b)) => {
+            let max = var_manager.new_lit();
+            let min = var_manager.new_lit();
+            collector.extend([
+                clause![!a, max],
+                clause![!b, max],
+                clause![!a, !b, min],
+                clause![!max, a, b],
+                clause![!min, a],
+                clause![!min, b],
+            ]);
+            (Some(max), Some(min))
+        }
+    }
+}
+
+/// Encodes a literal that is equivalent to the number of true literals in the
+/// sorted sequence being odd
+fn parity<Col>(
+    sorted: &[Option<Lit>],
+    collector: &mut Col,
+    var_manager: &mut dyn ManageVars,
+) -> Option<Lit>
+where
+    Col: CollectClauses,
+{
+    let first = sorted.first().copied().flatten()?;
+    let par = var_manager.new_lit();
+    collector.extend([clause![!par, first]]);
+    for (idx, &out) in sorted.iter().enumerate() {
+        let Some(out) = out else {
+            break;
+        };
+        let next = sorted.get(idx + 1).copied().flatten();
+        let mut cl = if idx % 2 == 0 {
+            // Exactly `idx + 1` true literals implies odd
+            clause![!out, par]
+        } else {
+            // Exactly `idx + 1` true literals implies even
+            clause![!out, !par]
+        };
+        if let Some(next) = next {
+            cl.add(next);
+        }
+        collector.extend([cl]);
+    }
+    Some(par)
+}
+
+impl Encode for SorterPb {
+    fn weight_sum(&self) -> usize {
+        self.weight_sum
+    }
+}
+
+impl IterWeightedInputs for SorterPb {
+    type Iter<'a> = super::gte::GteIter<'a>;
+
+    fn iter(&self) -> Self::Iter<'_> {
+        self.in_lits.iter().map(super::gte::copy_key_val)
+    }
+}
+
+impl BoundUpper for SorterPb {
+    fn encode_ub<Col, R>(&mut self, range: R, collector: &mut Col, var_manager: &mut dyn ManageVars)
+    where
+        Col: CollectClauses,
+        R: RangeBounds<usize>,
+    {
+        let range = super::prepare_ub_range(self, range);
+        if range.is_empty() {
+            return;
+        };
+        let n_vars_before = var_manager.n_used();
+        let n_clauses_before = collector.n_clauses();
+        if self.network.is_none() {
+            self.network = Some(self.build_network(collector, var_manager));
+        }
+        let network = self.network.as_ref().unwrap();
+        for ub in range {
+            self.ub_lits
+                .entry(ub)
+                .or_insert_with(|| SorterPb::encode_ub_lit(network, ub, collector, var_manager));
+        }
+        self.n_clauses += collector.n_clauses() - n_clauses_before;
+        self.n_vars += var_manager.n_used() - n_vars_before;
+    }
+
+    fn enforce_ub(&self, ub: usize) -> Result<Vec<Lit>, Error> {
+        if ub >= self.weight_sum {
+            return Ok(vec![]);
+        }
+        match self.ub_lits.get(&ub) {
+            Some(&olit) => Ok(vec![!olit]),
+            None => Err(Error::NotEncoded),
+        }
+    }
+}
+
+impl EncodeStats for SorterPb {
+    fn n_clauses(&self) -> usize {
+        self.n_clauses
+    }
+
+    fn n_vars(&self) -> u32 {
+        self.n_vars
+    }
+}
+
+impl From<RsHashMap<Lit, usize>> for SorterPb {
+    fn from(mut lits: RsHashMap<Lit, usize>) -> Self {
+        lits.retain(|_, w| *w > 0);
+        let weight_sum = lits.iter().fold(0, |sum, (_, w)| sum + *w);
+        Self {
+            in_lits: lits,
+            weight_sum,
+            ..Default::default()
+        }
+    }
+}
+
+impl FromIterator<(Lit, usize)> for SorterPb {
+    fn from_iter<T: IntoIterator<Item = (Lit, usize)>>(iter: T) -> Self {
+        let mut enc = Self::default();
+        enc.extend(iter);
+        enc
+    }
+}
+
+impl Extend<(Lit, usize)> for SorterPb {
+    /// Adds weighted input literals to the encoding. The weights of literals
+    /// that are added multiple times are summed up, also if the literal is
+    /// already in the encoding. Literals with weight zero are ignored.
+    fn extend<T: IntoIterator<Item = (Lit, usize)>>(&mut self, iter: T) {
+        iter.into_iter().for_each(|(l, w)| {
+            if w == 0 {
+                return;
+            }
+            self.weight_sum += w;
+            match self.in_lits.get_mut(&l) {
+                Some(old_w) => *old_w += w,
+                None => {
+                    self.in_lits.insert(l, w);
+                }
+            };
+        });
+        // The network needs to be rebuilt with the new inputs
+        self.network = None;
+        self.ub_lits.clear();
+    }
+}
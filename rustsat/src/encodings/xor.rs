@@ -0,0 +1,108 @@
+//! # Encodings for XOR Constraints
+//!
+//! Tseitin-style encoding of parity constraints of the form `l1 XOR l2 XOR ...
+//! XOR ln = rhs`.
+//!
+//! ## Example Useage
+//!
+//! ```
+//! # use rustsat::{encodings::xor, instances::{BasicVarManager, ManageVars}, lit, var};
+//! #
+//! let mut var_manager = BasicVarManager::default();
+//! var_manager.increase_next_free(var![5]);
+//! let lits = [lit![0], lit![1], lit![2], lit![3], lit![4]];
+//! let encoding = xor::encode_xor(&lits, true, &mut var_manager);
+//! ```
+
+use crate::{
+    instances::{Cnf, ManageVars},
+    types::{Clause, Lit},
+};
+
+/// Number of literals up to which an XOR is encoded directly without
+/// auxiliary variables
+const DIRECT_MAX: usize = 3;
+
+/// Encodes the parity constraint `l1 XOR l2 XOR ... XOR ln = rhs`. Up to three
+/// literals are encoded directly by excluding all assignments with the wrong
+/// parity. Longer constraints are split into a chain of auxiliary variables,
+/// each equivalent to the XOR of the previous auxiliary and the next literal,
+/// so that the number of clauses is linear in the number of literals.
+///
+/// An empty constraint with `rhs = true` is encoded as the empty clause.
+pub fn encode_xor(lits: &[Lit], rhs: bool, var_manager: &mut dyn ManageVars) -> Cnf {
+    let mut encoding = Cnf::new();
+    if lits.len() <= DIRECT_MAX {
+        encode_direct(lits, rhs, &mut encoding);
+        return encoding;
+    }
+    let mut acc = lits[0];
+    for &lit in &lits[1..lits.len() - 2] {
+        // `aux <-> acc XOR lit` is equivalent to `acc XOR lit XOR aux = false`
+        let aux = var_manager.new_lit();
+        encode_direct(&[acc, lit, aux], false, &mut encoding);
+        acc = aux;
+    }
+    encode_direct(
+        &[acc, lits[lits.len() - 2], lits[lits.len() - 1]],
+        rhs,
+        &mut encoding,
+    );
+    encoding
+}
+
+/// Encodes a parity constraint by adding one clause for each assignment to
+/// the literals with the wrong parity
+fn encode_direct(lits: &[Lit], rhs: bool, encoding: &mut Cnf) {
+    for mask in 0u32..1 << lits.len() {
+        if (mask.count_ones() % 2 == 1) == rhs {
+            continue;
+        }
+        let cl: Clause = lits
+            .iter()
+            .enumerate()
+            .map(|(idx, &l)| if mask & (1 << idx) != 0 { !l } else { l })
+            .collect();
+        encoding.add_clause(cl);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::encode_xor;
+    use crate::{
+        instances::{BasicVarManager, ManageVars},
+        lit,
+        test_utils::extendable,
+        types::Lit,
+        var,
+    };
+
+    #[test]
+    fn parity_exhaustive() {
+        for n_lits in 0..7u32 {
+            for rhs in [false, true] {
+                let mut var_manager = BasicVarManager::default();
+                var_manager.increase_next_free(var![n_lits]);
+                let lits: Vec<Lit> = (0..n_lits).map(|idx| lit![idx]).collect();
+                let encoding = encode_xor(&lits, rhs, &mut var_manager);
+                let n_vars = var_manager.n_used();
+                for inputs in 0u32..1 << n_lits {
+                    let sat = extendable(&encoding, &[], inputs, n_lits, n_vars);
+                    assert_eq!(sat, (inputs.count_ones() % 2 == 1) == rhs);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn small_xors_direct() {
+        let mut var_manager = BasicVarManager::default();
+        var_manager.increase_next_free(var![3]);
+        let encoding = encode_xor(&[lit![0], lit![1]], true, &mut var_manager);
+        assert_eq!(encoding.len(), 2);
+        let encoding = encode_xor(&[lit![0], lit![1], lit![2]], false, &mut var_manager);
+        assert_eq!(encoding.len(), 4);
+        assert_eq!(var_manager.n_used(), 3);
+    }
+}
@@ -9,7 +9,7 @@ use std::{
 };
 
 use crate::{
-    types::{Lit, RsHashMap, RsHasher, Var},
+    types::{Lit, RsHashMap, RsHashSet, RsHasher, Var},
     var,
 };
 
@@ -19,7 +19,7 @@ pub use sat::{Cnf, SatInstance};
 #[cfg(feature = "optimization")]
 mod opt;
 #[cfg(feature = "optimization")]
-pub use opt::{Objective, OptInstance};
+pub use opt::{lex_optimize, solve_maxsat, Objective, OptInstance};
 
 #[cfg(feature = "multiopt")]
 mod multiopt;
@@ -30,6 +30,7 @@ pub use multiopt::MultiOptInstance;
 use pyo3::prelude::*;
 
 pub mod fio;
+pub mod preprocess;
 
 /// Trait for variable managers keeping track of used variables
 pub trait ManageVars {
@@ -172,6 +173,221 @@ impl Default for BasicVarManager {
     }
 }
 
+/// Variable manager that recycles variables that are no longer needed.
+/// Released variables are kept on a free list and handed out again, most
+/// recently released first, before any fresh variable is used.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct RecyclingVarManager {
+    next_var: Var,
+    free: Vec<Var>,
+    released: RsHashSet<Var>,
+}
+
+impl RecyclingVarManager {
+    /// Creates a new variable manager from a next free variable
+    pub fn from_next_free(next_var: Var) -> Self {
+        Self {
+            next_var,
+            free: vec![],
+            released: RsHashSet::default(),
+        }
+    }
+
+    /// Releases a variable so that it can be handed out again. The variable
+    /// must not occur in any clause that is still used afterwards.
+    ///
+    /// # Panics
+    ///
+    /// If `var` has not been handed out by the manager or has already been
+    /// released.
+    pub fn release(&mut self, var: Var) {
+        assert!(var < self.next_var, "{} has not been handed out", var);
+        assert!(
+            self.released.insert(var),
+            "{} has already been released",
+            var
+        );
+        self.free.push(var);
+    }
+
+    /// Gets the number of released variables that have not been reused yet
+    pub fn n_free(&self) -> usize {
+        self.free.len()
+    }
+}
+
+impl Default for RecyclingVarManager {
+    fn default() -> Self {
+        Self::from_next_free(Var::new(0))
+    }
+}
+
+impl ManageVars for RecyclingVarManager {
+    fn new_var(&mut self) -> Var {
+        if let Some(v) = self.free.pop() {
+            self.released.remove(&v);
+            return v;
+        }
+        let v = self.next_var;
+        self.next_var += 1;
+        v
+    }
+
+    fn max_var(&self) -> Option<Var> {
+        if self.next_var == var![0] {
+            None
+        } else {
+            Some(self.next_var - 1)
+        }
+    }
+
+    /// Increases the next free variable. Released variables stay on the free
+    /// list, since they have been handed out by this manager before.
+    fn increase_next_free(&mut self, v: Var) -> bool {
+        if v > self.next_var {
+            self.next_var = v;
+            return true;
+        };
+        false
+    }
+
+    /// Combines two variable managers. A variable is only free afterwards if
+    /// it is free in both managers.
+    fn combine(&mut self, other: Self) {
+        let Self {
+            next_var: other_next,
+            free: other_free,
+            released: other_released,
+        } = other;
+        let released = &mut self.released;
+        self.free.retain(|v| {
+            let keep = *v >= other_next || other_released.contains(v);
+            if !keep {
+                released.remove(v);
+            }
+            keep
+        });
+        for v in other_free {
+            if v >= self.next_var {
+                self.released.insert(v);
+                self.free.push(v);
+            }
+        }
+        self.increase_next_free(other_next);
+    }
+
+    /// Gets the number of distinct variables handed out, i.e., the index of
+    /// the next fresh variable. Handing out a recycled variable does not
+    /// increase this number, so the [`crate::encodings::EncodeStats::n_vars`]
+    /// of an encoding built with this manager does not count reused
+    /// variables.
+    fn n_used(&self) -> u32 {
+        self.next_var.idx32()
+    }
+
+    fn forget_from(&mut self, min_var: Var) {
+        self.next_var = std::cmp::min(self.next_var, min_var);
+        let released = &mut self.released;
+        self.free.retain(|v| {
+            let keep = *v < min_var;
+            if !keep {
+                released.remove(v);
+            }
+            keep
+        });
+    }
+}
+
+/// Variable manager handing out variables only from a reserved range
+/// `[start, end)`. This is useful for confining the auxiliary variables of an
+/// encoding to a pre-reserved block of variable indices.
+///
+/// # Panics
+///
+/// Since [`ManageVars::new_var`] can not fail, requesting a variable from an
+/// exhausted manager via the trait panics. Use
+/// [`RangeVarManager::try_new_var`] for a fallible alternative.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct RangeVarManager {
+    start: Var,
+    end: Var,
+    next_var: Var,
+}
+
+impl RangeVarManager {
+    /// Creates a new variable manager for the range `[start, end)`
+    pub fn new(start: Var, end: Var) -> Self {
+        RangeVarManager {
+            start,
+            end: std::cmp::max(start, end),
+            next_var: start,
+        }
+    }
+
+    /// Gets the first variable of the range
+    pub fn start(&self) -> Var {
+        self.start
+    }
+
+    /// Gets the first variable after the range
+    pub fn end(&self) -> Var {
+        self.end
+    }
+
+    /// Gets the number of variables that can still be handed out
+    pub fn n_remaining(&self) -> u32 {
+        self.end.idx32() - self.next_var.idx32()
+    }
+
+    /// Uses up the next free variable, returning `None` if the range is exhausted
+    pub fn try_new_var(&mut self) -> Option<Var> {
+        if self.next_var >= self.end {
+            return None;
+        }
+        let v = self.next_var;
+        self.next_var += 1;
+        Some(v)
+    }
+}
+
+impl ManageVars for RangeVarManager {
+    fn new_var(&mut self) -> Var {
+        match self.try_new_var() {
+            Some(v) => v,
+            None => panic!("variable range [{}, {}) is exhausted", self.start, self.end),
+        }
+    }
+
+    fn max_var(&self) -> Option<Var> {
+        if self.next_var == self.start {
+            None
+        } else {
+            Some(self.next_var - 1)
+        }
+    }
+
+    fn increase_next_free(&mut self, v: Var) -> bool {
+        let v = std::cmp::min(v, self.end);
+        if v > self.next_var {
+            self.next_var = v;
+            return true;
+        };
+        false
+    }
+
+    fn combine(&mut self, other: Self) {
+        self.increase_next_free(other.next_var);
+    }
+
+    fn n_used(&self) -> u32 {
+        self.next_var.idx32()
+    }
+
+    fn forget_from(&mut self, min_var: Var) {
+        self.next_var = std::cmp::max(std::cmp::min(self.next_var, min_var), self.start)
+    }
+}
+
 /// Manager for reindexing an existing instance
 #[derive(PartialEq, Eq)]
 pub struct ReindexingVarManager {
@@ -484,7 +700,12 @@ impl Hash for Box<dyn VarKey> {
 
 #[cfg(test)]
 mod tests {
-    use super::{ManageVars, ObjectVarManager};
+    use super::{ManageVars, ObjectVarManager, RangeVarManager, RecyclingVarManager};
+    use crate::{
+        encodings::card::{BoundUpper, Totalizer},
+        instances::Cnf,
+        lit, var,
+    };
 
     #[test]
     fn var_manager_sequence() {
@@ -510,4 +731,93 @@ mod tests {
         assert_ne!(v1, v2);
         assert_eq!(v1, v3);
     }
+
+    #[test]
+    fn recycling_var_manager_lifo() {
+        let mut man = RecyclingVarManager::default();
+        let vars: Vec<_> = (0..4).map(|_| man.new_var()).collect();
+        assert_eq!(vars, vec![var![0], var![1], var![2], var![3]]);
+        man.release(var![1]);
+        man.release(var![3]);
+        assert_eq!(man.n_free(), 2);
+        assert_eq!(man.new_var(), var![3]);
+        assert_eq!(man.new_var(), var![1]);
+        assert_eq!(man.new_var(), var![4]);
+        assert_eq!(man.n_used(), 5);
+    }
+
+    #[test]
+    fn recycling_var_manager_bounds() {
+        let mut man = RecyclingVarManager::from_next_free(var![2]);
+        man.release(var![0]);
+        assert!(man.increase_next_free(var![5]));
+        assert_eq!(man.new_var(), var![0]);
+        assert_eq!(man.new_var(), var![5]);
+        man.release(var![4]);
+        man.release(var![1]);
+        man.forget_from(var![3]);
+        assert_eq!(man.n_free(), 1);
+        assert_eq!(man.new_var(), var![1]);
+        assert_eq!(man.new_var(), var![3]);
+    }
+
+    #[test]
+    fn recycling_var_manager_n_used() {
+        let mut man = RecyclingVarManager::default();
+        man.new_var();
+        man.new_var();
+        assert_eq!(man.n_used(), 2);
+        man.release(var![0]);
+        assert_eq!(man.n_used(), 2);
+        assert_eq!(man.new_var(), var![0]);
+        assert_eq!(man.n_used(), 2);
+        assert_eq!(man.new_var(), var![2]);
+        assert_eq!(man.n_used(), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "already been released")]
+    fn recycling_var_manager_double_release() {
+        let mut man = RecyclingVarManager::default();
+        man.new_var();
+        man.release(var![0]);
+        man.release(var![0]);
+    }
+
+    #[test]
+    fn range_var_manager() {
+        let mut man = RangeVarManager::new(var![5], var![7]);
+        assert_eq!(man.max_var(), None);
+        assert_eq!(man.new_var(), var![5]);
+        assert_eq!(man.try_new_var(), Some(var![6]));
+        assert_eq!(man.max_var(), Some(var![6]));
+        assert_eq!(man.try_new_var(), None);
+        man.forget_from(var![0]);
+        assert_eq!(man.max_var(), None);
+        assert_eq!(man.n_remaining(), 2);
+    }
+
+    #[test]
+    fn range_var_manager_confines_encoding() {
+        let mut man = RangeVarManager::new(var![10], var![100]);
+        let mut cnf = Cnf::new();
+        let mut enc = Totalizer::from(vec![lit![0], lit![1], lit![2], lit![3], lit![4]]);
+        enc.encode_ub(0..5, &mut cnf, &mut man);
+        assert!(man.max_var().is_some());
+        for cl in cnf {
+            for l in cl {
+                let v = l.var();
+                assert!(v < var![5] || (v >= var![10] && v < var![100]));
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "exhausted")]
+    fn range_var_manager_too_small() {
+        let mut man = RangeVarManager::new(var![10], var![12]);
+        let mut cnf = Cnf::new();
+        let mut enc = Totalizer::from(vec![lit![0], lit![1], lit![2], lit![3], lit![4]]);
+        enc.encode_ub(0..5, &mut cnf, &mut man);
+    }
 }
@@ -3,6 +3,13 @@
 //! As the submodules have different APIs, it is recommended to parse and write
 //! through the interface of instance types rather than using these functions
 //! directly.
+//!
+//! ## Compressed Files
+//!
+//! With feature `compression`, the `*_path` parsing functions of the instance
+//! types support bzip2, gzip and xz compressed files. The compression format
+//! is detected by the magic bytes at the start of the file, independent of the
+//! file extension.
 
 use std::{fs::File, io, path::Path};
 
@@ -38,25 +45,43 @@ impl From<opb::Error> for ParsingError {
     }
 }
 
+/// Magic bytes at the start of a gzip file
+#[cfg(feature = "compression")]
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+/// Magic bytes at the start of a bzip2 file
+#[cfg(feature = "compression")]
+const BZIP2_MAGIC: [u8; 3] = *b"BZh";
+/// Magic bytes at the start of an xz file
+#[cfg(feature = "compression")]
+const XZ_MAGIC: [u8; 6] = [0xfd, b'7', b'z', b'X', b'Z', 0x00];
+
 /// Opens a reader for the file at Path.
-/// With feature `compression` supports bzip2 and gzip compression.
+/// With feature `compression` supports compressed files, see the [module
+/// documentation](self#compressed-files).
 pub(crate) fn open_compressed_uncompressed_read<P: AsRef<Path>>(
     path: P,
 ) -> Result<Box<dyn io::Read>, io::Error> {
     let path = path.as_ref();
     let raw_reader = File::open(path)?;
     #[cfg(feature = "compression")]
-    if let Some(ext) = path.extension() {
-        if ext.eq_ignore_ascii_case(std::ffi::OsStr::new("bz2")) {
-            return Ok(Box::new(bzip2::read::BzDecoder::new(raw_reader)));
-        }
-        if ext.eq_ignore_ascii_case(std::ffi::OsStr::new("gz")) {
+    {
+        use io::BufRead;
+        // Peek at the magic bytes without consuming them, since FIFOs can not
+        // be rewound
+        let mut raw_reader = io::BufReader::new(raw_reader);
+        let start = raw_reader.fill_buf()?;
+        if start.starts_with(&GZIP_MAGIC) {
             return Ok(Box::new(flate2::read::GzDecoder::new(raw_reader)));
         }
-        if ext.eq_ignore_ascii_case(std::ffi::OsStr::new("xz")) {
+        if start.starts_with(&BZIP2_MAGIC) {
+            return Ok(Box::new(bzip2::read::BzDecoder::new(raw_reader)));
+        }
+        if start.starts_with(&XZ_MAGIC) {
             return Ok(Box::new(xz2::read::XzDecoder::new(raw_reader)));
         }
+        Ok(Box::new(raw_reader))
     }
+    #[cfg(not(feature = "compression"))]
     Ok(Box::new(raw_reader))
 }
 
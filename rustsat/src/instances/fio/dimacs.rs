@@ -25,6 +25,7 @@ use nom::{
     IResult,
 };
 use std::{
+    cell::Cell,
     convert::TryFrom,
     io::{self, BufRead, BufReader, Read, Write},
 };
@@ -50,15 +51,62 @@ where
     R: Read,
     VM: ManageVars + Default,
 {
-    let reader = BufReader::new(reader);
-    let content = parse_dimacs(reader)?;
-    #[cfg(not(feature = "optimization"))]
-    {
-        Ok(content)
-    }
+    parse_cnf_with_progress(reader, |_, _| ())
+}
+
+/// Number of clauses between two calls to the progress callback in
+/// [`parse_cnf_with_progress`]
+const PROGRESS_INTERVAL: usize = 100_000;
+
+/// Parses a CNF instance from a reader (typically a (compressed) file) and
+/// periodically reports progress. The callback is called with the number of
+/// clauses and bytes read so far every 100000 clauses and once more after
+/// parsing finished.
+pub fn parse_cnf_with_progress<R, VM, F>(
+    reader: R,
+    mut progress: F,
+) -> Result<SatInstance<VM>, Error>
+where
+    R: Read,
+    VM: ManageVars + Default,
+    F: FnMut(usize, usize),
+{
+    let n_bytes = Cell::new(0);
+    let reader = BufReader::new(CountingReader {
+        reader,
+        n_bytes: &n_bytes,
+    });
+    let (reader, preamble) = parse_preamble(reader)?;
+    let content = match preamble {
+        Preamble::Cnf { .. } => parse_cnf_body(reader, |n_clauses| {
+            if n_clauses.is_multiple_of(PROGRESS_INTERVAL) {
+                progress(n_clauses, n_bytes.get());
+            }
+        }),
+        #[cfg(feature = "optimization")]
+        Preamble::WcnfPre22 { top, .. } => parse_wcnf_pre22_body(reader, top),
+        #[cfg(feature = "optimization")]
+        Preamble::NoPLine { first_line } => parse_no_pline_body(reader, &first_line),
+    }?;
     #[cfg(feature = "optimization")]
-    {
-        Ok(content.0)
+    let inst = content.0;
+    #[cfg(not(feature = "optimization"))]
+    let inst = content;
+    progress(inst.n_clauses(), n_bytes.get());
+    Ok(inst)
+}
+
+/// Reader wrapper counting the number of bytes read
+struct CountingReader<'count, R> {
+    reader: R,
+    n_bytes: &'count Cell<usize>,
+}
+
+impl<R: Read> Read for CountingReader<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = self.reader.read(buf)?;
+        self.n_bytes.set(self.n_bytes.get() + len);
+        Ok(len)
     }
 }
 
@@ -194,7 +242,7 @@ where
         Preamble::Cnf {
             n_vars: _,    // Intentionally ignored (lean acceptance)
             n_clauses: _, // Intentionally ignored (lean acceptance)
-        } => parse_cnf_body(reader),
+        } => parse_cnf_body(reader, |_| ()),
         #[cfg(feature = "optimization")]
         Preamble::WcnfPre22 {
             n_vars: _,    // Intentionally ignored (lean acceptance)
@@ -249,11 +297,13 @@ fn parse_preamble<R: BufRead>(mut reader: R) -> Result<(R, Preamble), Error> {
     }
 }
 
-/// Main parser for CNF file
-fn parse_cnf_body<R, VM>(mut reader: R) -> Result<BodyContent<VM>, Error>
+/// Main parser for CNF file. `on_clause` is called with the number of
+/// clauses after every added clause.
+fn parse_cnf_body<R, VM, F>(mut reader: R, mut on_clause: F) -> Result<BodyContent<VM>, Error>
 where
     R: BufRead,
     VM: ManageVars + Default,
+    F: FnMut(usize),
 {
     let mut inst = SatInstance::<VM>::new();
     loop {
@@ -271,7 +321,8 @@ where
         };
         let (_, opt_clause) = parse_cnf_line(&buf).map_err(unwrap_dimacs_error)?;
         if let Some(clause) = opt_clause {
-            inst.add_clause(clause)
+            inst.add_clause(clause);
+            on_clause(inst.n_clauses());
         }
     }
 }
@@ -564,7 +615,7 @@ pub fn write_cnf_annotated<W: Write>(
         cnf.len()
     )?;
     cnf.into_iter()
-        .try_for_each(|cl| write_clause(writer, cl))?;
+        .try_for_each(|cl| write_clause(writer, &cl))?;
     writer.flush()
 }
 
@@ -583,7 +634,7 @@ pub fn write_cnf<W: Write, Iter: Iterator<Item = CnfLine>>(
 ) -> Result<(), io::Error> {
     data.try_for_each(|dat| match dat {
         CnfLine::Comment(c) => write!(writer, "c {}", c),
-        CnfLine::Clause(cl) => write_clause(writer, cl),
+        CnfLine::Clause(cl) => write_clause(writer, &cl),
     })
 }
 
@@ -597,24 +648,57 @@ pub fn write_wcnf_annotated<W: Write, CI: WClsIter>(
 ) -> Result<(), io::Error> {
     let (soft_cls, offset) = softs;
     let soft_cls: Vec<(Clause, usize)> = soft_cls.into_iter().collect();
-    writeln!(writer, "c WCNF file written by RustSAT")?;
-    if let Some(mv) = max_var {
-        writeln!(writer, "c highest var: {}", mv.pos_lit().to_ipasir())?;
-    }
-    writeln!(writer, "c {} hard clauses", cnf.len())?;
-    writeln!(writer, "c {} soft clauses", soft_cls.len())?;
-    writeln!(writer, "c objective offset: {}", offset)?;
+    write_wcnf_header(writer, cnf.len(), soft_cls.len(), offset, max_var)?;
     cnf.into_iter().try_for_each(|cl| {
         write!(writer, "h ")?;
-        write_clause(writer, cl)
+        write_clause(writer, &cl)
     })?;
     soft_cls.into_iter().try_for_each(|(cl, w)| {
         write!(writer, "{} ", w)?;
+        write_clause(writer, &cl)
+    })?;
+    writer.flush()
+}
+
+#[cfg(feature = "optimization")]
+/// Writes borrowed hard clauses and an objective to a (post 22, no p line)
+/// DIMACS WCNF file
+pub(crate) fn write_wcnf_annotated_borrowed<W: Write>(
+    writer: &mut W,
+    cnf: &Cnf,
+    obj: &Objective,
+    max_var: Option<Var>,
+) -> Result<(), io::Error> {
+    write_wcnf_header(writer, cnf.len(), obj.n_softs(), obj.offset(), max_var)?;
+    cnf.iter().try_for_each(|cl| {
+        write!(writer, "h ")?;
         write_clause(writer, cl)
     })?;
+    obj.iter_soft_cls_borrowed().try_for_each(|(cl, w)| {
+        write!(writer, "{} ", w)?;
+        write_clause(writer, &cl)
+    })?;
     writer.flush()
 }
 
+#[cfg(feature = "optimization")]
+/// Writes the comment header of a DIMACS WCNF file
+fn write_wcnf_header<W: Write>(
+    writer: &mut W,
+    n_hards: usize,
+    n_softs: usize,
+    offset: isize,
+    max_var: Option<Var>,
+) -> Result<(), io::Error> {
+    writeln!(writer, "c WCNF file written by RustSAT")?;
+    if let Some(mv) = max_var {
+        writeln!(writer, "c highest var: {}", mv.pos_lit().to_ipasir())?;
+    }
+    writeln!(writer, "c {} hard clauses", n_hards)?;
+    writeln!(writer, "c {} soft clauses", n_softs)?;
+    writeln!(writer, "c objective offset: {}", offset)
+}
+
 #[cfg(feature = "optimization")]
 /// Input data for writing a single-objective (WCNF) instance
 pub enum WcnfLine {
@@ -636,11 +720,11 @@ pub fn write_wcnf<W: Write, Iter: Iterator<Item = WcnfLine>>(
         WcnfLine::Comment(c) => write!(writer, "c {}", c),
         WcnfLine::Hard(cl) => {
             write!(writer, "h ")?;
-            write_clause(writer, cl)
+            write_clause(writer, &cl)
         }
         WcnfLine::Soft(cl, w) => {
             write!(writer, "{} ", w)?;
-            write_clause(writer, cl)
+            write_clause(writer, &cl)
         }
     })
 }
@@ -676,7 +760,7 @@ pub fn write_mcnf_annotated<W: Write, CI: WClsIter>(
     writeln!(writer, ")")?;
     cnf.into_iter().try_for_each(|cl| {
         write!(writer, "h ")?;
-        write_clause(writer, cl)
+        write_clause(writer, &cl)
     })?;
     soft_cls
         .into_iter()
@@ -684,7 +768,7 @@ pub fn write_mcnf_annotated<W: Write, CI: WClsIter>(
         .try_for_each(|(idx, sft_cls)| {
             sft_cls.into_iter().try_for_each(|(cl, w)| {
                 write!(writer, "o{} {} ", idx + 1, w)?;
-                write_clause(writer, cl)
+                write_clause(writer, &cl)
             })
         })?;
     writer.flush()
@@ -711,18 +795,18 @@ pub fn write_mcnf<W: Write, Iter: Iterator<Item = McnfLine>>(
         McnfLine::Comment(c) => writeln!(writer, "c {}", c),
         McnfLine::Hard(cl) => {
             write!(writer, "h ")?;
-            write_clause(writer, cl)
+            write_clause(writer, &cl)
         }
         McnfLine::Soft(cl, w, oidx) => {
             write!(writer, "o{} {} ", oidx + 1, w)?;
-            write_clause(writer, cl)
+            write_clause(writer, &cl)
         }
     })
 }
 
-fn write_clause<W: Write>(writer: &mut W, clause: Clause) -> Result<(), io::Error> {
+pub(crate) fn write_clause<W: Write>(writer: &mut W, clause: &Clause) -> Result<(), io::Error> {
     clause
-        .into_iter()
+        .iter()
         .try_for_each(|l| write!(writer, "{} ", l.to_ipasir()))?;
     writeln!(writer, "0")
 }
@@ -1044,7 +1128,7 @@ mod tests {
         let data = "1 2 0\n-3 4 5 0\n";
         let reader = Cursor::new(data);
 
-        let parsed_inst = parse_cnf_body(reader).unwrap();
+        let parsed_inst = parse_cnf_body(reader, |_| ()).unwrap();
 
         let mut true_inst: SatInstance = SatInstance::new();
         true_inst.add_clause(clause![ipasir_lit![1], ipasir_lit![2]]);
@@ -1174,6 +1258,46 @@ mod tests {
         assert_eq!(parsed_inst, (true_constrs, vec![true_obj0, true_obj1]));
     }
 
+    #[test]
+    fn parse_cnf_progress() {
+        let data = "c test\np cnf 3 3\n1 2 0\n-1 3 0\n-2 -3 0\n";
+        let mut calls = vec![];
+        let inst: SatInstance =
+            super::parse_cnf_with_progress(data.as_bytes(), |n_clauses, n_bytes| {
+                calls.push((n_clauses, n_bytes))
+            })
+            .unwrap();
+        assert_eq!(inst.n_clauses(), 3);
+        assert_eq!(calls, vec![(3, data.len())]);
+    }
+
+    #[test]
+    fn parse_cnf_progress_interval() {
+        let n_clauses = 2 * super::PROGRESS_INTERVAL + 1;
+        let mut data = format!("p cnf 2 {n_clauses}\n");
+        for _ in 0..n_clauses {
+            data.push_str("1 -2 0\n");
+        }
+        let mut calls = vec![];
+        let inst: SatInstance =
+            super::parse_cnf_with_progress(data.as_bytes(), |n_clauses, n_bytes| {
+                calls.push((n_clauses, n_bytes))
+            })
+            .unwrap();
+        assert_eq!(inst.n_clauses(), n_clauses);
+        let counts: Vec<usize> = calls.iter().map(|&(n_clauses, _)| n_clauses).collect();
+        assert_eq!(
+            counts,
+            vec![
+                super::PROGRESS_INTERVAL,
+                2 * super::PROGRESS_INTERVAL,
+                n_clauses
+            ]
+        );
+        assert!(calls.windows(2).all(|w| w[0].1 <= w[1].1));
+        assert_eq!(calls.last().unwrap().1, data.len());
+    }
+
     #[test]
     fn write_parse_cnf() {
         let mut true_cnf = Cnf::new();
@@ -1217,6 +1341,50 @@ mod tests {
         assert_eq!(parsed_inst, OptInstance::compose(true_constrs, true_obj));
     }
 
+    #[cfg(feature = "optimization")]
+    #[test]
+    fn write_parse_wcnf_writer() {
+        let mut constrs: SatInstance = SatInstance::new();
+        let mut obj = Objective::new();
+        constrs.add_clause(clause![ipasir_lit![1], ipasir_lit![2]]);
+        obj.add_soft_clause(10, clause![ipasir_lit![-3], ipasir_lit![4], ipasir_lit![5]]);
+        obj.add_soft_clause(3, clause![ipasir_lit![-1]]);
+        let true_inst = OptInstance::compose(constrs, obj);
+
+        let mut cursor = Cursor::new(vec![]);
+        true_inst.to_dimacs_wcnf_writer(&mut cursor).unwrap();
+        let written = String::from_utf8(cursor.get_ref().clone()).unwrap();
+        assert!(written.lines().any(|line| line == "h 1 2 0"));
+        assert!(written.lines().any(|line| line == "10 -3 4 5 0"));
+        assert!(written.lines().any(|line| line == "3 -1 0"));
+
+        cursor.rewind().unwrap();
+        let parsed_inst = super::parse_wcnf_with_idx(cursor, 0).unwrap();
+
+        assert_eq!(parsed_inst, true_inst);
+    }
+
+    #[cfg(feature = "optimization")]
+    #[test]
+    fn wcnf_accessors() {
+        let pre22 = "p wcnf 3 4 10\n10 1 2 0\n10 -1 -3 0\n3 -2 0\n5 3 0\n";
+        let post22 = "h 1 2 0\nh -1 -3 0\n3 -2 0\n5 3 0\n";
+        for data in [pre22, post22] {
+            let inst: OptInstance = OptInstance::from_dimacs(data.as_bytes()).unwrap();
+            let mut hards = Cnf::new();
+            hards.add_clause(clause![ipasir_lit![1], ipasir_lit![2]]);
+            hards.add_clause(clause![ipasir_lit![-1], ipasir_lit![-3]]);
+            assert_eq!(inst.hard_clauses(), &hards);
+            assert_eq!(inst.n_soft(), 2);
+            let mut softs = inst.soft_clauses();
+            softs.sort_by_key(|(_, w)| *w);
+            assert_eq!(
+                softs,
+                vec![(clause![ipasir_lit![-2]], 3), (clause![ipasir_lit![3]], 5)]
+            );
+        }
+    }
+
     #[cfg(feature = "multiopt")]
     #[test]
     fn write_parse_mcnf() {
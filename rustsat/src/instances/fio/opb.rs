@@ -34,7 +34,7 @@ use thiserror::Error;
 #[cfg(feature = "multiopt")]
 use crate::instances::MultiOptInstance;
 #[cfg(feature = "optimization")]
-use crate::instances::{Objective, OptInstance};
+use crate::instances::{BasicVarManager, Cnf, Objective, OptInstance};
 #[cfg(feature = "optimization")]
 use crate::types::WLitIter;
 
@@ -253,10 +253,17 @@ fn weight(input: &str) -> IResult<&str, isize> {
     map_res(i64, |i| i.try_into())(input)
 }
 
-/// Parses an OPB weighted term
+/// Parses an OPB weighted term. Besides separating the weight and the
+/// literal with spaces, we also allow for an explicit multiplication (e.g.,
+/// `3*x1` or `3 * x1`).
 fn weighted_literal(input: &str, opts: Options) -> IResult<&str, (Lit, isize)> {
     map_res(
-        tuple((weight, space1, |i| literal(i, opts), space0)),
+        tuple((
+            weight,
+            alt((recognize(tuple((space0, tag("*"), space0))), space1)),
+            |i| literal(i, opts),
+            space0,
+        )),
         |(w, _, l, _)| Ok::<_, ()>((l, w)),
     )(input)
 }
@@ -308,19 +315,21 @@ fn constraint(input: &str, opts: Options) -> IResult<&str, PBConstraint> {
 }
 
 #[cfg(feature = "optimization")]
-/// Parses an OPB objective
+/// Parses an OPB objective. Maximization objectives (`max:`) are converted to
+/// minimization objectives by negating the weights.
 fn objective(input: &str, opts: Options) -> IResult<&str, Objective> {
     map_res(
         tuple((
-            tag("min:"),
+            alt((tag("min:"), tag("max:"))),
             space0,
             |i| weighted_lit_sum(i, opts),
             opb_ending,
         )),
-        |(_, _, wsl, _)| {
+        |(sense, _, wsl, _)| {
             let mut obj = Objective::new();
-            wsl.into_iter()
-                .for_each(|(l, w)| obj.increase_soft_lit_int(w, l));
+            wsl.into_iter().for_each(|(l, w)| {
+                obj.increase_soft_lit_int(if sense == "max:" { -w } else { w }, l)
+            });
             Ok::<_, ()>(obj)
         },
     )(input)
@@ -330,7 +339,7 @@ fn objective(input: &str, opts: Options) -> IResult<&str, Objective> {
 /// Matches an OPB objective
 fn objective(input: &str, opts: Options) -> IResult<&str, &str> {
     recognize(tuple((
-        tag("min:"),
+        alt((tag("min:"), tag("max:"))),
         space0,
         |i| weighted_lit_sum(i, opts),
         opb_ending,
@@ -441,6 +450,59 @@ where
     writer.flush()
 }
 
+#[cfg(feature = "optimization")]
+/// Writes pseudo-boolean constraints and an optional objective to an OPB file.
+/// Soft clauses in the objective are relaxed with fresh variables above the
+/// highest variable in the constraints and the objective.
+pub fn write_opb_writer<W: Write>(
+    writer: &mut W,
+    pbs: &[PBConstraint],
+    obj: Option<&Objective>,
+    opts: Options,
+) -> Result<(), io::Error> {
+    let max_var = pbs
+        .iter()
+        .flat_map(|pb| pb.iter().map(|(l, _)| l.var()))
+        .chain(obj.and_then(Objective::max_var))
+        .max();
+    let mut vm = BasicVarManager::default();
+    if let Some(max_var) = max_var {
+        vm.increase_next_free(max_var + 1);
+    }
+    let (hardened, softs) = match obj {
+        Some(obj) => {
+            let (hardened, softs) = obj.clone().as_soft_lits(&mut vm);
+            (hardened, Some(softs))
+        }
+        None => (Cnf::new(), None),
+    };
+    writeln!(
+        writer,
+        "* #variable = {} #constraint= {}",
+        vm.n_used(),
+        hardened.len() + pbs.len()
+    )?;
+    writeln!(writer, "* OPB file written by RustSAT")?;
+    if let Some(max_var) = vm.max_var() {
+        writeln!(writer, "* maximum variable: {}", max_var)?;
+    }
+    writeln!(writer, "* {} pseudo-boolean constraints", pbs.len())?;
+    writeln!(
+        writer,
+        "* {} relaxed and hardened soft clauses",
+        hardened.len()
+    )?;
+    if let Some(softs) = softs {
+        write_objective(writer, softs, opts)?;
+    }
+    hardened
+        .into_iter()
+        .try_for_each(|cl| write_clause(writer, cl, opts))?;
+    pbs.iter()
+        .try_for_each(|pb| write_pb(writer, pb.clone(), opts))?;
+    writer.flush()
+}
+
 #[cfg(feature = "multiopt")]
 /// Writes a [`MultiOptInstance`] to an OPB file
 pub fn write_multi_opt<W, VM>(
@@ -705,7 +767,12 @@ mod test {
     #[cfg(feature = "optimization")]
     use super::{opb_data, parse_opb_data, Error, OpbData};
     #[cfg(feature = "optimization")]
-    use crate::instances::Objective;
+    use super::{parse_opt_with_idx, parse_sat, write_opb_writer, write_opt};
+    #[cfg(feature = "optimization")]
+    use crate::{
+        instances::{Objective, OptInstance},
+        test_utils::all_assignments,
+    };
     #[cfg(feature = "optimization")]
     use std::io::BufReader;
 
@@ -800,6 +867,14 @@ mod test {
             weighted_literal("-5 ~x1 test", Options::default()),
             Ok(("test", (!lit![0], -5)))
         );
+        assert_eq!(
+            weighted_literal("5*x1 test", Options::default()),
+            Ok(("test", (lit![0], 5)))
+        );
+        assert_eq!(
+            weighted_literal("-5 * ~x1 test", Options::default()),
+            Ok(("test", (!lit![0], -5)))
+        );
     }
 
     #[test]
@@ -849,6 +924,16 @@ mod test {
             }
             Err(_) => panic!(),
         }
+        match objective("max: 3 x1 -2 ~x2;", Options::default()) {
+            Ok((rest, obj)) => {
+                assert_eq!(rest, "");
+                let mut should_be_obj = Objective::new();
+                should_be_obj.increase_soft_lit_int(-3, lit![0]);
+                should_be_obj.increase_soft_lit_int(2, !lit![1]);
+                assert_eq!(obj, should_be_obj);
+            }
+            Err(_) => panic!(),
+        }
         match objective("min: x0;", Options::default()) {
             Ok(_) => panic!(),
             Err(err) => assert_eq!(err, nom::Err::Error(NomError::new("x0;", ErrorKind::Eof))),
@@ -1064,4 +1149,85 @@ mod test {
         true_inst.add_pb_constr(PBConstraint::new_lb(lits.clone(), 2));
         write_parse_inst_test(true_inst.clone(), true_inst, alt_opb_opts);
     }
+
+    #[cfg(feature = "optimization")]
+    #[test]
+    fn write_parse_opt() {
+        let mut constrs: SatInstance = SatInstance::new();
+        constrs.add_clause(clause![lit![0], !lit![1]]);
+        constrs.add_card_constr(CardConstraint::new_ub(vec![lit![1], lit![2], lit![3]], 2));
+        constrs.add_pb_constr(PBConstraint::new_lb(
+            vec![(lit![0], 3), (!lit![2], 2), (lit![3], 1)],
+            3,
+        ));
+        let mut obj = Objective::new();
+        obj.increase_soft_lit_int(4, lit![1]);
+        obj.increase_soft_lit_int(-2, lit![3]);
+        let true_inst = OptInstance::compose(constrs, obj);
+
+        let mut cursor = Cursor::new(vec![]);
+        write_opt(&mut cursor, true_inst.clone(), Options::default()).unwrap();
+        cursor.rewind().unwrap();
+        let parsed_inst: OptInstance = parse_opt_with_idx(cursor, 0, Options::default()).unwrap();
+
+        // All constraints are parsed as pseudo-boolean lower bounds over
+        // positive weights
+        assert_eq!(
+            parsed_inst.constrs.pbs,
+            vec![
+                PBConstraint::new_lb(vec![(lit![0], 1), (!lit![1], 1)], 1),
+                PBConstraint::new_lb(vec![(!lit![1], 1), (!lit![2], 1), (!lit![3], 1)], 1),
+                PBConstraint::new_lb(vec![(lit![0], 3), (!lit![2], 2), (lit![3], 1)], 3),
+            ]
+        );
+        assert_eq!(parsed_inst.constrs.n_clauses(), 0);
+        assert_eq!(parsed_inst.constrs.n_cards(), 0);
+        // The instances must agree on the cost of every assignment
+        for assign in all_assignments(4) {
+            assert_eq!(parsed_inst.cost(&assign), true_inst.cost(&assign));
+        }
+    }
+
+    #[cfg(feature = "optimization")]
+    #[test]
+    fn write_parse_pbs() {
+        let reader = BufReader::new(std::fs::File::open("./data/tiny-opt.opb").unwrap());
+        let true_inst: OptInstance = parse_opt_with_idx(reader, 0, Options::default()).unwrap();
+
+        for opts in [
+            Options::default(),
+            Options {
+                no_negated_lits: false,
+                ..Options::default()
+            },
+        ] {
+            let mut cursor = Cursor::new(vec![]);
+            write_opb_writer(
+                &mut cursor,
+                &true_inst.constrs.pbs,
+                Some(&true_inst.obj),
+                opts,
+            )
+            .unwrap();
+            cursor.rewind().unwrap();
+            let parsed_inst: OptInstance = parse_opt_with_idx(cursor, 0, opts).unwrap();
+
+            assert_eq!(parsed_inst.constrs.pbs, true_inst.constrs.pbs);
+            for assign in all_assignments(4) {
+                assert_eq!(parsed_inst.cost(&assign), true_inst.cost(&assign));
+            }
+        }
+
+        let mut cursor = Cursor::new(vec![]);
+        write_opb_writer(
+            &mut cursor,
+            &true_inst.constrs.pbs,
+            None,
+            Options::default(),
+        )
+        .unwrap();
+        cursor.rewind().unwrap();
+        let parsed_inst: SatInstance = parse_sat(cursor, Options::default()).unwrap();
+        assert_eq!(parsed_inst.pbs, true_inst.constrs.pbs);
+    }
 }
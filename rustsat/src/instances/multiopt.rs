@@ -305,7 +305,7 @@ impl<VM: ManageVars + Default> MultiOptInstance<VM> {
 
     /// Parses an OPB instance from a file path. For more details see
     /// [`MultiOptInstance::from_opb`]. With feature `compression` supports
-    /// bzip2 and gzip compression, detected by the file extension.
+    /// compressed files, see [`fio`](crate::instances::fio#compressed-files).
     pub fn from_opb_path<P: AsRef<Path>>(
         path: P,
         opts: fio::opb::Options,
@@ -1,13 +1,19 @@
 //! # Optimization Instance Representations
 
-use std::{cmp, io, path::Path};
+use std::{borrow::Cow, cmp, io, path::Path};
 
 use crate::{
     clause,
-    encodings::{card, pb},
+    encodings::{
+        card::{self, Totalizer},
+        pb::{self, BoundUpper, BoundUpperIncremental, GeneralizedTotalizer},
+        IterWeightedInputs,
+    },
+    solvers::{SolveIncremental, SolverError, SolverResult},
     types::{
         constraints::{CardConstraint, PBConstraint},
-        Assignment, Clause, ClsIter, Lit, LitIter, RsHashMap, TernaryVal, Var, WClsIter, WLitIter,
+        Assignment, Clause, ClsIter, Lit, LitIter, RsHashMap, RsHashSet, TernaryVal, Var, WClsIter,
+        WLitIter,
     },
 };
 
@@ -687,6 +693,42 @@ impl Objective {
         }
     }
 
+    /// Iterates over the soft clauses of the objective with their weights
+    /// without cloning them. Soft literals are yielded as unit clauses
+    /// containing their negation.
+    pub(crate) fn iter_soft_cls_borrowed(
+        &self,
+    ) -> Box<dyn Iterator<Item = (Cow<'_, Clause>, usize)> + '_> {
+        match &self.0 {
+            IntObj::Unweighted {
+                soft_clauses,
+                soft_lits,
+                unit_weight,
+                ..
+            } => Box::new(
+                soft_clauses
+                    .iter()
+                    .map(Cow::Borrowed)
+                    .chain(soft_lits.iter().map(|&l| Cow::Owned(clause![!l])))
+                    .map(|cl| (cl, unit_weight.unwrap())),
+            ),
+            IntObj::Weighted {
+                soft_clauses,
+                soft_lits,
+                ..
+            } => Box::new(
+                soft_clauses
+                    .iter()
+                    .map(|(cl, &w)| (Cow::Borrowed(cl), w))
+                    .chain(
+                        soft_lits
+                            .iter()
+                            .map(|(&l, &w)| (Cow::Owned(clause![!l]), w)),
+                    ),
+            ),
+        }
+    }
+
     /// Gets the maximum variable in the objective
     pub fn max_var(&self) -> Option<Var> {
         let find_max = |mv, v| {
@@ -977,6 +1019,25 @@ impl<VM: ManageVars> OptInstance<VM> {
         )
     }
 
+    /// Writes the instance to DIMACS WCNF (post 22) without consuming it.
+    /// Hard clauses are prefixed with `h` and soft clauses with their weight.
+    /// Purely clausal instances are written directly, non-clausal constraints
+    /// are encoded with the default encoders on a copy of the instance.
+    pub fn to_dimacs_wcnf_writer<W: io::Write>(&self, writer: &mut W) -> Result<(), io::Error>
+    where
+        VM: Clone,
+    {
+        if self.constrs.n_cards() > 0 || self.constrs.n_pbs() > 0 {
+            return self.clone().to_dimacs(writer);
+        }
+        fio::dimacs::write_wcnf_annotated_borrowed(
+            writer,
+            &self.constrs.cnf,
+            &self.obj,
+            self.constrs.var_manager.max_var(),
+        )
+    }
+
     /// Writes the instance to DIMACS WCNF (post 22) converting non-clausal
     /// constraints with specific encoders.
     pub fn to_dimacs_with_encoders<W, CardEnc, PBEnc>(
@@ -1014,6 +1075,26 @@ impl<VM: ManageVars> OptInstance<VM> {
         fio::opb::write_opt::<W, VM>(writer, self, opts)
     }
 
+    /// Gets the hard clauses of the instance. Note that this does not include
+    /// cardinality and pseudo-boolean constraints in the instance.
+    pub fn hard_clauses(&self) -> &Cnf {
+        &self.constrs.cnf
+    }
+
+    /// Gets the soft clauses of the objective with their weights. Soft literals
+    /// are returned as unit clauses containing their negation.
+    pub fn soft_clauses(&self) -> Vec<(Clause, usize)> {
+        self.obj
+            .iter_soft_cls_borrowed()
+            .map(|(cl, w)| (cl.into_owned(), w))
+            .collect()
+    }
+
+    /// Gets the number of soft literals and clauses in the objective
+    pub fn n_soft(&self) -> usize {
+        self.obj.n_softs()
+    }
+
     /// Calculates the objective value of an assignment. Returns [`None`] if the
     /// assignment is not a solution.
     pub fn cost(&self, assign: &Assignment) -> Option<isize> {
@@ -1061,7 +1142,7 @@ impl<VM: ManageVars + Default> OptInstance<VM> {
 
     /// Parses a DIMACS instance from a file path. For more details see
     /// [`OptInstance::from_dimacs`]. With feature `compression` supports
-    /// bzip2 and gzip compression, detected by the file extension.
+    /// compressed files, see [`fio`](crate::instances::fio#compressed-files).
     pub fn from_dimacs_path<P: AsRef<Path>>(path: P) -> Result<Self, fio::ParsingError> {
         match fio::open_compressed_uncompressed_read(path) {
             Err(why) => Err(fio::ParsingError::IO(why)),
@@ -1071,7 +1152,7 @@ impl<VM: ManageVars + Default> OptInstance<VM> {
 
     /// Parses a DIMACS instance from a file path. For more details see
     /// [`OptInstance::from_dimacs_with_idx`]. With feature `compression` supports
-    /// bzip2 and gzip compression, detected by the file extension.
+    /// compressed files, see [`fio`](crate::instances::fio#compressed-files).
     pub fn from_dimacs_path_with_idx<P: AsRef<Path>>(
         path: P,
         obj_idx: usize,
@@ -1109,7 +1190,7 @@ impl<VM: ManageVars + Default> OptInstance<VM> {
 
     /// Parses an OPB instance from a file path. For more details see
     /// [`OptInstance::from_opb`]. With feature `compression` supports
-    /// bzip2 and gzip compression, detected by the file extension.
+    /// compressed files, see [`fio`](crate::instances::fio#compressed-files).
     pub fn from_opb_path<P: AsRef<Path>>(
         path: P,
         opts: fio::opb::Options,
@@ -1123,8 +1204,8 @@ impl<VM: ManageVars + Default> OptInstance<VM> {
     /// Parses an OPB instance from a file path, selecting the objective with
     /// index `obj_idx` if multiple are available. The index starts at 0. For
     /// more details see [`OptInstance::from_opb`]. With feature
-    /// `compression` supports bzip2 and gzip compression, detected by the file
-    /// extension.
+    /// `compression` supports compressed files, see
+    /// [`fio`](crate::instances::fio#compressed-files).
     pub fn from_opb_path_with_idx<P: AsRef<Path>>(
         path: P,
         obj_idx: usize,
@@ -1152,3 +1233,331 @@ impl<VM: ManageVars + Default> FromIterator<WcnfLine> for OptInstance<VM> {
         inst
     }
 }
+
+/// Lexicographically minimizes multiple pseudo-boolean objectives. The first
+/// objective is minimized to optimality by tightening the bound of a
+/// [`GeneralizedTotalizer`] until the solver reports unsatisfiability. Its
+/// optimum is then fixed with hard clauses before the next objective is
+/// minimized. Returns the optimal value of each objective in order, or `None`
+/// if the clauses in the solver are unsatisfiable.
+///
+/// Since the optima are fixed with hard clauses, the solver stays restricted
+/// to lexicographically optimal solutions after this returns.
+///
+/// # Errors
+///
+/// - [`SolverError::Interrupted`] if a solver call is interrupted
+/// - Any error returned by the solver
+///
+/// # Examples
+///
+/// ```
+/// # use rustsat::{instances::{lex_optimize, BasicVarManager, ManageVars}, lit, solvers::Solve, types::RsHashMap, var};
+/// let mut solver = rustsat_minisat::core::Minisat::default();
+/// let mut var_manager = BasicVarManager::default();
+/// var_manager.increase_next_free(var![2]);
+/// solver.add_binary(lit![0], lit![1]).unwrap();
+/// let first = RsHashMap::from_iter([(lit![0], 2), (lit![1], 1)]);
+/// let second = RsHashMap::from_iter([(!lit![0], 1)]);
+/// let optima = lex_optimize(&mut solver, vec![first, second], &mut var_manager).unwrap();
+/// assert_eq!(optima, Some(vec![1, 1]));
+/// ```
+pub fn lex_optimize<S>(
+    solver: &mut S,
+    objectives: Vec<RsHashMap<Lit, usize>>,
+    var_manager: &mut dyn ManageVars,
+) -> Result<Option<Vec<usize>>, SolverError>
+where
+    S: SolveIncremental + ?Sized,
+{
+    /// Evaluates the objective encoded by `gte` on the solver's current solution
+    fn cost<S: SolveIncremental + ?Sized>(
+        solver: &S,
+        gte: &GeneralizedTotalizer,
+    ) -> Result<usize, SolverError> {
+        gte.iter().try_fold(0, |sum, (lit, weight)| {
+            Ok(if solver.lit_val(lit)? == TernaryVal::True {
+                sum + weight
+            } else {
+                sum
+            })
+        })
+    }
+    /// Checks a solver result, mapping interruptions to errors
+    fn is_sat(res: SolverResult) -> Result<bool, SolverError> {
+        match res {
+            SolverResult::Sat => Ok(true),
+            SolverResult::Unsat => Ok(false),
+            SolverResult::Interrupted => Err(SolverError::Interrupted),
+        }
+    }
+
+    let mut optima = Vec::with_capacity(objectives.len());
+    for obj in objectives {
+        let mut gte = GeneralizedTotalizer::from(obj);
+        // All earlier optima are fixed by hard clauses, so this is only
+        // unsatisfiable for the first objective
+        if !is_sat(solver.solve()?)? {
+            return Ok(None);
+        }
+        let mut opt = cost(solver, &gte)?;
+        while opt > 0 {
+            let mut cnf = Cnf::new();
+            gte.encode_ub_change(opt - 1..opt, &mut cnf, var_manager);
+            solver.add_cnf(cnf)?;
+            let assumps = gte
+                .enforce_ub(opt - 1)
+                .expect("bound should have been encoded");
+            if !is_sat(solver.solve_assumps(&assumps)?)? {
+                break;
+            }
+            opt = cost(solver, &gte)?;
+        }
+        // Fix the optimum with units rather than assumptions, so that it also
+        // holds for the following objectives
+        let mut cnf = Cnf::new();
+        gte.encode_ub_change(opt..=opt, &mut cnf, var_manager);
+        solver.add_cnf(cnf)?;
+        for lit in gte.enforce_ub(opt).expect("bound should have been encoded") {
+            solver.add_unit(lit)?;
+        }
+        optima.push(opt);
+    }
+    if optima.is_empty() && !is_sat(solver.solve()?)? {
+        return Ok(None);
+    }
+    Ok(Some(optima))
+}
+
+/// Solves a weighted MaxSAT problem with the core-guided OLL algorithm. The
+/// clauses in the solver are the hard clauses and `soft_clauses` are the soft
+/// clauses with their weights. Each soft clause is relaxed with a fresh
+/// blocking literal whose negation is assumed. Whenever the assumptions are
+/// unsatisfiable, the minimum weight of the assumptions in the core is added
+/// to the lower bound and subtracted from the weights of the assumptions in
+/// the core. The core is then relaxed by a [`Totalizer`] over its literals,
+/// allowing one more of them to be violated at the cost of the minimum
+/// weight. If all soft clauses have weight one, weights are not tracked and
+/// unit soft clauses are assumed directly instead of being relaxed with a
+/// blocking literal.
+///
+/// Returns the minimum total weight of falsified soft clauses together with a
+/// solution achieving it, or `None` if the hard clauses are unsatisfiable.
+/// The relaxation clauses stay in the solver.
+///
+/// # Errors
+///
+/// - [`SolverError::Interrupted`] if a solver call is interrupted
+/// - Any error returned by the solver
+///
+/// # References
+///
+/// - Antonio Morgado and Carmine Dodaro and Joao Marques-Silva: _Core-Guided
+///   MaxSAT with Soft Cardinality Constraints_, CP 2014.
+///
+/// # Examples
+///
+/// ```
+/// # use rustsat::{clause, instances::{solve_maxsat, BasicVarManager, ManageVars}, lit, solvers::Solve, var};
+/// let mut solver = rustsat_minisat::core::Minisat::default();
+/// let mut var_manager = BasicVarManager::default();
+/// var_manager.increase_next_free(var![2]);
+/// solver.add_binary(!lit![0], !lit![1]).unwrap();
+/// let softs = vec![(clause![lit![0]], 3), (clause![lit![1]], 2)];
+/// let (cost, _) = solve_maxsat(&mut solver, softs, &mut var_manager).unwrap().unwrap();
+/// assert_eq!(cost, 2);
+/// ```
+pub fn solve_maxsat<S>(
+    solver: &mut S,
+    soft_clauses: Vec<(Clause, usize)>,
+    var_manager: &mut dyn ManageVars,
+) -> Result<Option<(usize, Assignment)>, SolverError>
+where
+    S: SolveIncremental + ?Sized,
+{
+    // The maximum variable of the problem, the model is truncated to this
+    let max_var = soft_clauses
+        .iter()
+        .filter_map(|(cl, _)| cl.iter().map(|l| l.var()).max())
+        .chain(var_manager.max_var())
+        .max();
+    if soft_clauses.iter().all(|(_, w)| *w == 1) {
+        return solve_maxsat_unweighted(solver, soft_clauses, max_var, var_manager);
+    }
+    // The assumptions with their remaining weight
+    let mut assumps: RsHashMap<Lit, usize> = RsHashMap::default();
+    for (cl, w) in &soft_clauses {
+        if *w == 0 {
+            continue;
+        }
+        let blit = var_manager.new_lit();
+        let mut relaxed = cl.clone();
+        relaxed.add(blit);
+        solver.add_clause(relaxed)?;
+        assumps.insert(!blit, *w);
+    }
+    // The totalizers relaxing the cores and, for each assumption on a
+    // totalizer output, the totalizer and the enforced bound
+    let mut totalizers: Vec<Totalizer> = vec![];
+    let mut tot_assumps: RsHashMap<Lit, (usize, usize)> = RsHashMap::default();
+    let mut lower_bound = 0;
+    loop {
+        let assump_lits: Vec<Lit> = assumps.keys().copied().collect();
+        match solver.solve_assumps(&assump_lits)? {
+            SolverResult::Sat => break,
+            SolverResult::Unsat => (),
+            SolverResult::Interrupted => return Err(SolverError::Interrupted),
+        }
+        let core = solver.core()?;
+        if core.is_empty() {
+            return Ok(None);
+        }
+        let core_weight = core
+            .iter()
+            .map(|&l| assumps[&!l])
+            .min()
+            .expect("core is not empty");
+        lower_bound += core_weight;
+        for &l in &core {
+            let w = assumps.get_mut(&!l).unwrap();
+            *w -= core_weight;
+            if *w == 0 {
+                assumps.remove(&!l);
+            }
+            // A violated totalizer bound is relaxed to the next bound
+            if let Some(&(tot_idx, ub)) = tot_assumps.get(&!l) {
+                let tot = &mut totalizers[tot_idx];
+                if ub + 1 < card::Encode::n_lits(tot) {
+                    let next = relax_bound(tot, ub + 1, solver, var_manager)?;
+                    *assumps.entry(next).or_default() += core_weight;
+                    tot_assumps.insert(next, (tot_idx, ub + 1));
+                }
+            }
+        }
+        if core.len() == 1 {
+            // The only literal in the core must be violated
+            solver.add_unit(core[0])?;
+            continue;
+        }
+        let mut tot = Totalizer::from(core);
+        let next = relax_bound(&mut tot, 1, solver, var_manager)?;
+        *assumps.entry(next).or_default() += core_weight;
+        tot_assumps.insert(next, (totalizers.len(), 1));
+        totalizers.push(tot);
+    }
+    maxsat_solution(solver, &soft_clauses, max_var, lower_bound)
+}
+
+/// Unweighted variant of [`solve_maxsat`] for soft clauses that all have
+/// weight one. Every core increases the lower bound by one and all of its
+/// assumptions are removed, so no weights need to be tracked.
+fn solve_maxsat_unweighted<S>(
+    solver: &mut S,
+    soft_clauses: Vec<(Clause, usize)>,
+    max_var: Option<Var>,
+    var_manager: &mut dyn ManageVars,
+) -> Result<Option<(usize, Assignment)>, SolverError>
+where
+    S: SolveIncremental + ?Sized,
+{
+    let mut assumps: Vec<Lit> = Vec::with_capacity(soft_clauses.len());
+    // Literals of unit soft clauses that are assumed directly. Repeated unit
+    // soft clauses are relaxed with a blocking literal so that every
+    // assumption stands for exactly one soft clause.
+    let mut direct: RsHashSet<Lit> = RsHashSet::default();
+    for (cl, _) in &soft_clauses {
+        if cl.len() == 1 && direct.insert(cl[0]) {
+            assumps.push(cl[0]);
+            continue;
+        }
+        let blit = var_manager.new_lit();
+        let mut relaxed = cl.clone();
+        relaxed.add(blit);
+        solver.add_clause(relaxed)?;
+        assumps.push(!blit);
+    }
+    // The totalizers relaxing the cores and, for each assumption on a
+    // totalizer output, the totalizer and the enforced bound
+    let mut totalizers: Vec<Totalizer> = vec![];
+    let mut tot_assumps: RsHashMap<Lit, (usize, usize)> = RsHashMap::default();
+    let mut lower_bound = 0;
+    loop {
+        match solver.solve_assumps(&assumps)? {
+            SolverResult::Sat => break,
+            SolverResult::Unsat => (),
+            SolverResult::Interrupted => return Err(SolverError::Interrupted),
+        }
+        let core = solver.core()?;
+        if core.is_empty() {
+            return Ok(None);
+        }
+        lower_bound += 1;
+        let in_core: RsHashSet<Lit> = core.iter().map(|&l| !l).collect();
+        assumps.retain(|a| !in_core.contains(a));
+        for &l in &core {
+            // A violated totalizer bound is relaxed to the next bound
+            if let Some((tot_idx, ub)) = tot_assumps.remove(&!l) {
+                let tot = &mut totalizers[tot_idx];
+                if ub + 1 < card::Encode::n_lits(tot) {
+                    let next = relax_bound(tot, ub + 1, solver, var_manager)?;
+                    assumps.push(next);
+                    tot_assumps.insert(next, (tot_idx, ub + 1));
+                }
+            }
+        }
+        if core.len() == 1 {
+            // The only literal in the core must be violated
+            solver.add_unit(core[0])?;
+            continue;
+        }
+        let mut tot = Totalizer::from(core);
+        let next = relax_bound(&mut tot, 1, solver, var_manager)?;
+        assumps.push(next);
+        tot_assumps.insert(next, (totalizers.len(), 1));
+        totalizers.push(tot);
+    }
+    maxsat_solution(solver, &soft_clauses, max_var, lower_bound)
+}
+
+/// Gets the optimal solution after the final satisfiable call of
+/// [`solve_maxsat`] and its cost, which must match the lower bound
+fn maxsat_solution<S>(
+    solver: &mut S,
+    soft_clauses: &[(Clause, usize)],
+    max_var: Option<Var>,
+    lower_bound: usize,
+) -> Result<Option<(usize, Assignment)>, SolverError>
+where
+    S: SolveIncremental + ?Sized,
+{
+    let sol = match max_var {
+        Some(max_var) => solver.solution(max_var)?,
+        None => Assignment::default(),
+    };
+    let cost = soft_clauses
+        .iter()
+        .filter(|(cl, _)| !cl.iter().any(|&l| sol.lit_value(l) == TernaryVal::True))
+        .map(|(_, w)| w)
+        .sum();
+    debug_assert_eq!(cost, lower_bound);
+    Ok(Some((cost, sol)))
+}
+
+/// Encodes the upper bound `ub` in a totalizer relaxing a core and returns
+/// the assumption enforcing it
+fn relax_bound<S>(
+    tot: &mut Totalizer,
+    ub: usize,
+    solver: &mut S,
+    var_manager: &mut dyn ManageVars,
+) -> Result<Lit, SolverError>
+where
+    S: SolveIncremental + ?Sized,
+{
+    let mut cnf = Cnf::new();
+    card::BoundUpperIncremental::encode_ub_change(tot, ub..ub + 1, &mut cnf, var_manager);
+    solver.add_cnf(cnf)?;
+    let assumps = card::BoundUpper::enforce_ub(tot, ub).expect("bound should have been encoded");
+    debug_assert_eq!(assumps.len(), 1);
+    Ok(assumps[0])
+}
@@ -0,0 +1,168 @@
+//! # Preprocessing Techniques for CNF Formulas
+//!
+//! Simplifications that can be applied to a [`Cnf`] before solving. The
+//! simplifications preserve satisfiability, but not necessarily equivalence.
+//! Clauses that are removed are recorded on a [`ReconstructionStack`] that
+//! extends models of the simplified formula to models of the original one.
+//!
+//! ## References
+//!
+//! - Matti Järvisalo and Armin Biere and Marijn Heule: _Blocked Clause
+//!   Elimination_, TACAS 2010.
+
+use std::collections::VecDeque;
+
+use crate::types::{Assignment, Clause, Lit, RsHashMap, RsHashSet, TernaryVal};
+
+use super::Cnf;
+
+/// Stack of removed clauses needed to reconstruct a model of the original
+/// formula from a model of a preprocessed formula
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ReconstructionStack {
+    /// The removed clauses and the literal they are blocked on, in order of
+    /// removal
+    stack: Vec<(Lit, Clause)>,
+}
+
+impl ReconstructionStack {
+    /// Gets the number of removed clauses on the stack
+    pub fn len(&self) -> usize {
+        self.stack.len()
+    }
+
+    /// Checks if the stack is empty
+    pub fn is_empty(&self) -> bool {
+        self.stack.is_empty()
+    }
+
+    /// Extends a model of the preprocessed formula to a model of the original
+    /// formula. The removed clauses are processed in reverse order of removal
+    /// and the blocking literal is set to true for every clause that is not
+    /// satisfied.
+    pub fn extend_model(&self, model: &mut Assignment) {
+        for (blocking, cl) in self.stack.iter().rev() {
+            if !cl.iter().any(|&l| model.lit_value(l) == TernaryVal::True) {
+                model.assign_lit(*blocking);
+            }
+        }
+    }
+}
+
+/// Removes all blocked clauses from a CNF formula and returns the number of
+/// removed clauses. A clause is blocked on a literal `l` in it if all
+/// resolvents with clauses containing `!l` are tautologies. Clauses are
+/// rechecked after a clause containing the negation of one of their literals
+/// is removed, until a fixpoint is reached.
+///
+/// Blocked clause elimination preserves satisfiability, but a model of the
+/// simplified formula might not satisfy the removed clauses. The removed
+/// clauses are therefore pushed onto `reconstruction`, see
+/// [`ReconstructionStack::extend_model`].
+pub fn eliminate_blocked(cnf: &mut Cnf, reconstruction: &mut ReconstructionStack) -> usize {
+    let clauses = &cnf.clauses;
+    let mut occs: RsHashMap<Lit, Vec<usize>> = RsHashMap::default();
+    for (idx, cl) in clauses.iter().enumerate() {
+        for &lit in cl {
+            occs.entry(lit).or_default().push(idx);
+        }
+    }
+    let mut removed = vec![false; clauses.len()];
+    let mut queued = vec![true; clauses.len()];
+    let mut queue: VecDeque<usize> = (0..clauses.len()).collect();
+    let mut n_removed = 0;
+    while let Some(idx) = queue.pop_front() {
+        queued[idx] = false;
+        if removed[idx] {
+            continue;
+        }
+        let cl = &clauses[idx];
+        let lits: RsHashSet<Lit> = cl.iter().copied().collect();
+        let Some(blocking) = cl.iter().copied().find(|&lit| {
+            occs.get(&!lit).is_none_or(|others| {
+                others.iter().all(|&other| {
+                    removed[other]
+                        || clauses[other]
+                            .iter()
+                            .any(|&l| l != !lit && lits.contains(&!l))
+                })
+            })
+        }) else {
+            continue;
+        };
+        removed[idx] = true;
+        n_removed += 1;
+        reconstruction.stack.push((blocking, cl.clone()));
+        // Clauses containing the negation of a literal in the removed clause
+        // might be blocked now
+        for &lit in cl {
+            for &other in occs.get(&!lit).into_iter().flatten() {
+                if !removed[other] && !queued[other] {
+                    queued[other] = true;
+                    queue.push_back(other);
+                }
+            }
+        }
+    }
+    if n_removed > 0 {
+        let clauses = std::mem::take(cnf).clauses;
+        *cnf = clauses
+            .into_iter()
+            .zip(removed)
+            .filter_map(|(cl, removed)| (!removed).then_some(cl))
+            .collect();
+    }
+    n_removed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{eliminate_blocked, ReconstructionStack};
+    use crate::{clause, instances::Cnf, lit, test_utils::all_assignments};
+
+    #[test]
+    fn pure_literal_blocked() {
+        let mut cnf = Cnf::new();
+        cnf.add_binary(lit![0], lit![1]);
+        cnf.add_binary(lit![0], !lit![1]);
+        let mut rec = ReconstructionStack::default();
+        assert_eq!(eliminate_blocked(&mut cnf, &mut rec), 2);
+        assert!(cnf.is_empty());
+        assert_eq!(rec.len(), 2);
+    }
+
+    #[test]
+    fn nothing_blocked() {
+        let mut cnf = Cnf::new();
+        cnf.add_binary(lit![0], lit![1]);
+        cnf.add_binary(!lit![0], lit![1]);
+        cnf.add_binary(lit![0], !lit![1]);
+        cnf.add_binary(!lit![0], !lit![1]);
+        let mut rec = ReconstructionStack::default();
+        assert_eq!(eliminate_blocked(&mut cnf, &mut rec), 0);
+        assert_eq!(cnf.len(), 4);
+        assert!(rec.is_empty());
+    }
+
+    #[test]
+    fn reconstruct_model() {
+        // equivalence a <-> b is blocked on both literals, c is unconstrained
+        let mut cnf = Cnf::new();
+        cnf.add_binary(!lit![0], lit![1]);
+        cnf.add_binary(lit![0], !lit![1]);
+        cnf.add_clause(clause![lit![1], lit![2], !lit![3]]);
+        cnf.add_clause(clause![!lit![1], lit![3]]);
+        let orig = cnf.clone();
+        let mut rec = ReconstructionStack::default();
+        let n_removed = eliminate_blocked(&mut cnf, &mut rec);
+        assert_eq!(n_removed + cnf.len(), orig.len());
+        assert!(n_removed > 0);
+        for mut model in all_assignments(4) {
+            if !cnf.is_satisfied(&model) {
+                continue;
+            }
+            rec.extend_model(&mut model);
+            assert!(orig.is_satisfied(&model));
+        }
+    }
+}
@@ -1,14 +1,21 @@
 //! # Satsifiability Instance Representations
 
-use std::{collections::TryReserveError, io, ops::Index, path::Path};
+use std::{
+    collections::{BTreeSet, TryReserveError},
+    hash::{Hash, Hasher},
+    io,
+    ops::{AddAssign, Index},
+    path::Path,
+};
 
 use crate::{
     clause,
-    encodings::{atomics, card, pb, CollectClauses},
+    encodings::{self, atomics, card, pb, CollectClauses},
     lit,
+    solvers::{SolveIncremental, SolverError, SolverResult},
     types::{
         constraints::{CardConstraint, PBConstraint},
-        Assignment, Clause, Lit, Var,
+        Assignment, Clause, Lit, RsHashMap, RsHasher, TernaryVal, Var,
     },
 };
 
@@ -29,9 +36,15 @@ use super::{
 /// type only supports clauses and does have an internal variable manager.
 #[cfg_attr(feature = "pyapi", pyclass)]
 #[derive(Clone, Eq, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
 pub struct Cnf {
     pub(super) clauses: Vec<Clause>,
     #[cfg(feature = "pyapi")]
+    #[cfg_attr(feature = "serde", serde(skip))]
     modified: bool,
 }
 
@@ -94,6 +107,17 @@ impl Cnf {
         self.clauses.len()
     }
 
+    /// Returns the total number of literal occurrences in all clauses
+    pub fn n_lits(&self) -> usize {
+        self.clauses.iter().map(Clause::len).sum()
+    }
+
+    /// Adds a clone of a clause to the CNF, for when the clause is still
+    /// needed afterwards
+    pub fn add_clause_ref(&mut self, clause: &Clause) {
+        self.add_clause(clause.clone())
+    }
+
     /// See [`atomics::lit_impl_lit`]
     pub fn add_lit_impl_lit(&mut self, a: Lit, b: Lit) {
         self.add_clause(atomics::lit_impl_lit(a, b))
@@ -139,6 +163,16 @@ impl Cnf {
         self.extend(atomics::cube_impl_cube(a, b))
     }
 
+    /// See [`atomics::encode_equiv`]
+    pub fn add_equiv(&mut self, a: Lit, b: Lit) {
+        self.extend(atomics::encode_equiv(a, b))
+    }
+
+    /// Appends all clauses of another CNF, preserving their order
+    pub fn extend_from_cnf(&mut self, other: Cnf) {
+        self.extend(other);
+    }
+
     /// Joins the current CNF with another one. Like [`Cnf::extend`] but
     /// consumes the object and returns a new object.
     pub fn join(mut self, other: Cnf) -> Cnf {
@@ -156,6 +190,51 @@ impl Cnf {
         self.clauses.iter_mut()
     }
 
+    /// Checks whether an assignment satisfies all clauses. Variables assigned
+    /// [`TernaryVal::DontCare`] are treated as unassigned, i.e., a clause only
+    /// containing such variables is not satisfied.
+    pub fn is_satisfied(&self, assignment: &Assignment) -> bool {
+        self.clauses.iter().all(|cl| cl.is_sat(assignment))
+    }
+
+    /// Renames the variables used in the clauses to the contiguous range
+    /// `0..n`, preserving their relative order and the polarity of all
+    /// literals. Returns the mapping from old to new variables, which can be
+    /// used to translate assignments back.
+    pub fn compact_vars(&mut self) -> RsHashMap<Var, Var> {
+        let used: BTreeSet<Var> = self
+            .clauses
+            .iter()
+            .flat_map(|cl| cl.iter().map(|l| l.var()))
+            .collect();
+        let map: RsHashMap<Var, Var> = used
+            .into_iter()
+            .enumerate()
+            .map(|(idx, v)| (v, Var::new(idx as u32)))
+            .collect();
+        self.clauses.iter_mut().for_each(|cl| {
+            cl.iter_mut().for_each(|l| {
+                let v = map[&l.var()];
+                *l = if l.is_pos() { v.pos_lit() } else { v.neg_lit() };
+            })
+        });
+        map
+    }
+
+    /// Parses a CNF from a DIMACS reader. Any variables not occurring in
+    /// clauses are ignored.
+    pub fn from_dimacs<R: io::Read>(reader: R) -> Result<Self, fio::ParsingError> {
+        let inst: SatInstance<BasicVarManager> = SatInstance::from_dimacs(reader)?;
+        Ok(inst.as_cnf().0)
+    }
+
+    /// Parses a CNF from a DIMACS file path. With feature `compression`
+    /// supports compressed files, see [`fio`](crate::instances::fio#compressed-files).
+    pub fn from_dimacs_path<P: AsRef<Path>>(path: P) -> Result<Self, fio::ParsingError> {
+        let inst: SatInstance<BasicVarManager> = SatInstance::from_dimacs_path(path)?;
+        Ok(inst.as_cnf().0)
+    }
+
     /// Normalizes the CNF. This includes normalizing and sorting the clauses,
     /// removing duplicates and tautologies. Comparing two normalized CNFs
     /// is equal to comparing sets of sets of literals.
@@ -182,6 +261,175 @@ impl Cnf {
         }
     }
 
+    /// Checks whether a partial assignment is an autarky for the CNF, i.e.,
+    /// whether every clause touched by the assignment is satisfied by it.
+    /// Variables assigned to [`TernaryVal::DontCare`] count as unassigned.
+    pub fn is_autarky(&self, partial: &Assignment) -> bool {
+        self.iter()
+            .all(|cl| !Self::touches(cl, partial) || cl.evaluate(partial) == TernaryVal::True)
+    }
+
+    /// Removes all clauses touched by an autarky. Removing these clauses
+    /// preserves satisfiability. The caller is responsible for making sure
+    /// that `partial` actually is an autarky, see [`Cnf::is_autarky`].
+    pub fn apply_autarky(&mut self, partial: &Assignment) {
+        self.clauses.retain(|cl| !Self::touches(cl, partial));
+        #[cfg(feature = "pyapi")]
+        {
+            self.modified = true;
+        }
+    }
+
+    /// Simplifies the CNF by unit propagation. Unit clauses are repeatedly
+    /// assigned, clauses satisfied by the assignment are removed and falsified
+    /// literals are removed from the remaining clauses. Returns the assigned
+    /// literals in order of propagation, or `None` if propagation derives an
+    /// empty clause. The unit clauses themselves are removed as well, so the
+    /// simplified CNF together with the returned literals is equivalent to the
+    /// original CNF. If a conflict is found, the CNF is replaced by a single
+    /// empty clause.
+    pub fn propagate_units(&mut self) -> Option<Vec<Lit>> {
+        let (assigned, conflict) = self.propagate_units_with_conflict();
+        if conflict {
+            self.clauses = vec![Clause::new()];
+            return None;
+        }
+        Some(assigned)
+    }
+
+    /// Same as [`Cnf::propagate_units`], but returns the literals assigned
+    /// before a conflict and whether a conflict was found. On a conflict, the
+    /// CNF is left partially simplified.
+    pub(crate) fn propagate_units_with_conflict(&mut self) -> (Vec<Lit>, bool) {
+        #[cfg(feature = "pyapi")]
+        {
+            self.modified = true;
+        }
+        let mut assignment = Assignment::default();
+        let mut assigned = vec![];
+        loop {
+            let mut conflict = false;
+            let n_assigned = assigned.len();
+            self.clauses.retain_mut(|cl| {
+                if conflict {
+                    return true;
+                }
+                if cl
+                    .iter()
+                    .any(|&l| assignment.lit_value(l) == TernaryVal::True)
+                {
+                    return false;
+                }
+                if cl
+                    .iter()
+                    .any(|&l| assignment.lit_value(l) == TernaryVal::False)
+                {
+                    *cl = cl
+                        .iter()
+                        .copied()
+                        .filter(|&l| assignment.lit_value(l) == TernaryVal::DontCare)
+                        .collect();
+                }
+                match cl.len() {
+                    0 => {
+                        conflict = true;
+                        true
+                    }
+                    1 => {
+                        let unit = cl.lits()[0];
+                        assignment.assign_lit(unit);
+                        assigned.push(unit);
+                        false
+                    }
+                    _ => true,
+                }
+            });
+            if conflict || assigned.len() == n_assigned {
+                return (assigned, conflict);
+            }
+        }
+    }
+
+    /// Lowers a [`SatInstance`] to CNF by encoding all cardinality and
+    /// pseudo-boolean constraints with the default encodings and adding them
+    /// to the clauses of the instance. Auxiliary variables are taken from
+    /// `var_manager`, which is first made aware of all variables in the
+    /// instance.
+    ///
+    /// # Errors
+    ///
+    /// [`encodings::Error::Unsat`] if any of the constraints is trivially
+    /// unsatisfiable.
+    pub fn try_from_instance<VM: ManageVars>(
+        instance: &SatInstance<VM>,
+        var_manager: &mut dyn ManageVars,
+    ) -> Result<Cnf, encodings::Error> {
+        if instance.cards.iter().any(|card| card.is_unsat())
+            || instance.pbs.iter().any(|pb| pb.is_unsat())
+        {
+            return Err(encodings::Error::Unsat);
+        }
+        if let Some(max_var) = instance.var_manager.max_var() {
+            var_manager.mark_used(max_var);
+        }
+        let mut cnf = instance.cnf.clone();
+        instance.cards.iter().for_each(|card| {
+            card::default_encode_cardinality_constraint(card.clone(), &mut cnf, var_manager)
+        });
+        instance
+            .pbs
+            .iter()
+            .for_each(|pb| pb::default_encode_pb_constraint(pb.clone(), &mut cnf, var_manager));
+        Ok(cnf)
+    }
+
+    /// Writes the CNF to a DIMACS CNF file. The number of variables in the
+    /// header is determined from the highest variable in the clauses.
+    pub fn to_dimacs_writer<W: io::Write>(&self, writer: &mut W) -> Result<(), io::Error> {
+        let n_vars = self
+            .iter()
+            .flat_map(|cl| cl.iter())
+            .map(|l| l.var().idx32() + 1)
+            .max()
+            .unwrap_or(0);
+        self.to_dimacs_writer_with_n_vars(writer, n_vars)
+    }
+
+    /// Writes the CNF to a DIMACS CNF file with an explicit number of
+    /// variables in the header, e.g., if variables were reserved but do not
+    /// appear in any clause.
+    pub fn to_dimacs_writer_with_n_vars<W: io::Write>(
+        &self,
+        writer: &mut W,
+        n_vars: u32,
+    ) -> Result<(), io::Error> {
+        writeln!(writer, "c CNF file written by RustSAT")?;
+        writeln!(writer, "p cnf {} {}", n_vars, self.len())?;
+        self.iter()
+            .try_for_each(|cl| fio::dimacs::write_clause(writer, cl))?;
+        writer.flush()
+    }
+
+    /// Checks whether the CNF is trivially satisfiable or unsatisfiable,
+    /// without invoking a solver. Returns [`SolverResult::Sat`] for an empty
+    /// CNF, [`SolverResult::Unsat`] if the CNF contains the empty clause and
+    /// [`None`] otherwise.
+    pub fn trivial_result(&self) -> Option<SolverResult> {
+        if self.clauses.is_empty() {
+            return Some(SolverResult::Sat);
+        }
+        if self.clauses.iter().any(|cl| cl.is_empty()) {
+            return Some(SolverResult::Unsat);
+        }
+        None
+    }
+
+    /// Checks whether a clause contains a variable assigned in a partial assignment
+    fn touches(cl: &Clause, partial: &Assignment) -> bool {
+        cl.iter()
+            .any(|&l| partial.lit_value(l) != TernaryVal::DontCare)
+    }
+
     #[cfg(feature = "rand")]
     /// Randomly shuffles the order of clauses in the CNF
     pub fn shuffle(mut self) -> Self {
@@ -208,6 +456,17 @@ impl IntoIterator for Cnf {
     }
 }
 
+impl<'a> IntoIterator for &'a Cnf {
+    type Item = &'a Clause;
+
+    type IntoIter = std::slice::Iter<'a, Clause>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.clauses.iter()
+    }
+}
+
 impl FromIterator<Clause> for Cnf {
     fn from_iter<T: IntoIterator<Item = Clause>>(iter: T) -> Self {
         Self {
@@ -233,6 +492,12 @@ impl Extend<Clause> for Cnf {
     }
 }
 
+impl AddAssign<Cnf> for Cnf {
+    fn add_assign(&mut self, rhs: Cnf) {
+        self.extend_from_cnf(rhs)
+    }
+}
+
 impl Index<usize> for Cnf {
     type Output = Clause;
 
@@ -697,6 +962,72 @@ impl<VM: ManageVars> SatInstance<VM> {
         }
     }
 
+    /// Computes a hash of the instance that is independent of the order of
+    /// constraints and literals, of duplicate clauses and of gaps in the
+    /// variable indices. For this, the used variables are compacted to a
+    /// contiguous range (preserving their order) and all constraints are
+    /// normalized before hashing. Variable names stored in the variable
+    /// manager are ignored.
+    ///
+    /// Two instances therefore hash equally if one is obtained from the other
+    /// by an _order-preserving_ renaming of the variables, i.e., one where
+    /// `x < y` implies that `x` is renamed to a lower variable than `y`. The
+    /// hash is not invariant under renamings that change the order of the
+    /// variables, since canonicalizing those would require solving graph
+    /// isomorphism.
+    pub fn canonical_hash(&self) -> u64 {
+        let used: BTreeSet<Var> = self
+            .cnf
+            .iter()
+            .flat_map(|cl| cl.iter().map(|l| l.var()))
+            .chain(self.cards.iter().flat_map(|c| c.iter().map(|l| l.var())))
+            .chain(self.pbs.iter().flat_map(|c| c.iter().map(|(l, _)| l.var())))
+            .collect();
+        let compact: RsHashMap<Var, Var> = used
+            .into_iter()
+            .enumerate()
+            .map(|(idx, v)| (v, Var::new(idx as u32)))
+            .collect();
+        let rename = |l: &mut Lit| {
+            let v = compact[&l.var()];
+            *l = if l.is_pos() { v.pos_lit() } else { v.neg_lit() };
+        };
+
+        let mut cnf = self.cnf.clone();
+        cnf.iter_mut().for_each(|cl| cl.iter_mut().for_each(rename));
+        let cnf = cnf.normalize();
+        let mut cards: Vec<u64> = self
+            .cards
+            .iter()
+            .map(|card| {
+                let mut card = card.clone();
+                card.iter_mut().for_each(rename);
+                let mut hasher = RsHasher::default();
+                card.normalize().hash(&mut hasher);
+                hasher.finish()
+            })
+            .collect();
+        cards.sort_unstable();
+        let mut pbs: Vec<u64> = self
+            .pbs
+            .iter()
+            .map(|pb| {
+                let mut pb = pb.clone();
+                pb.iter_mut().for_each(|(l, _)| rename(l));
+                let mut hasher = RsHasher::default();
+                pb.normalize().hash(&mut hasher);
+                hasher.finish()
+            })
+            .collect();
+        pbs.sort_unstable();
+
+        let mut hasher = RsHasher::default();
+        cnf.iter().for_each(|cl| cl.hash(&mut hasher));
+        cards.hash(&mut hasher);
+        pbs.hash(&mut hasher);
+        hasher.finish()
+    }
+
     #[cfg(feature = "rand")]
     /// Randomly shuffles the order of constraints.
     pub fn shuffle(mut self) -> Self {
@@ -846,10 +1177,8 @@ impl<VM: ManageVars> SatInstance<VM> {
     }
 
     pub fn is_sat(&self, assign: &Assignment) -> bool {
-        for clause in self.cnf.iter() {
-            if !clause.is_sat(assign) {
-                return false;
-            }
+        if !self.cnf.is_satisfied(assign) {
+            return false;
         }
         for card in &self.cards {
             if !card.is_sat(assign) {
@@ -863,6 +1192,72 @@ impl<VM: ManageVars> SatInstance<VM> {
         }
         true
     }
+
+    /// Finds a model of the instance that is subset-minimal with respect to
+    /// the literals in `objective` being true. The instance is loaded into
+    /// `solver`, a model is found and then, one objective literal at a time,
+    /// the solver is asked whether the literal can be made false while keeping
+    /// all already falsified objective literals false. The returned model is
+    /// restricted to the variables of the instance and the objective.
+    ///
+    /// Returns [`None`] if the instance is unsatisfiable or the initial solver
+    /// call is interrupted. If a later call is interrupted, the corresponding
+    /// objective literal is kept true and the model might not be minimal.
+    ///
+    /// # Errors
+    ///
+    /// If the solver fails.
+    pub fn solve_minimal_model<S: SolveIncremental>(
+        &self,
+        solver: &mut S,
+        objective: &[Lit],
+    ) -> Result<Option<Assignment>, SolverError> {
+        let mut var_manager = BasicVarManager::default();
+        objective.iter().for_each(|l| {
+            var_manager.mark_used(l.var());
+        });
+        let high_var = match (self.var_manager.max_var(), var_manager.max_var()) {
+            (Some(v1), Some(v2)) => Some(std::cmp::max(v1, v2)),
+            (v, None) | (None, v) => v,
+        };
+        let Ok(cnf) = Cnf::try_from_instance(self, &mut var_manager) else {
+            return Ok(None);
+        };
+        solver.add_cnf(cnf)?;
+        if solver.solve()? != SolverResult::Sat {
+            return Ok(None);
+        }
+        let Some(high_var) = high_var else {
+            // Satisfiable instance without any variables
+            return Ok(Some(Assignment::default()));
+        };
+        let mut model = solver.solution(high_var)?;
+        let mut assumps: Vec<Lit> = objective
+            .iter()
+            .filter(|&&l| model.lit_value(l) != TernaryVal::True)
+            .map(|&l| !l)
+            .collect();
+        for &lit in objective {
+            if model.lit_value(lit) != TernaryVal::True {
+                continue;
+            }
+            assumps.push(!lit);
+            if solver.solve_assumps(&assumps)? == SolverResult::Sat {
+                model = solver.solution(high_var)?;
+                // Fix all objective literals that became false in the new model
+                assumps.clear();
+                assumps.extend(
+                    objective
+                        .iter()
+                        .filter(|&&l| model.lit_value(l) != TernaryVal::True)
+                        .map(|&l| !l),
+                );
+            } else {
+                assumps.pop();
+            }
+        }
+        Ok(Some(model))
+    }
 }
 
 impl<VM: ManageVars + Default> SatInstance<VM> {
@@ -887,7 +1282,7 @@ impl<VM: ManageVars + Default> SatInstance<VM> {
 
     /// Parses a DIMACS instance from a file path. For more details see
     /// [`SatInstance::from_dimacs`]. With feature `compression` supports
-    /// bzip2 and gzip compression, detected by the file extension.
+    /// compressed files, see [`fio`](crate::instances::fio#compressed-files).
     pub fn from_dimacs_path<P: AsRef<Path>>(path: P) -> Result<Self, fio::ParsingError> {
         match fio::open_compressed_uncompressed_read(path) {
             Err(why) => Err(fio::ParsingError::IO(why)),
@@ -895,6 +1290,21 @@ impl<VM: ManageVars + Default> SatInstance<VM> {
         }
     }
 
+    /// Parses a DIMACS instance from a reader object, like
+    /// [`SatInstance::from_dimacs`], and reports progress via a callback. The
+    /// callback is called with the number of clauses and bytes read so far
+    /// every 100000 clauses and once after parsing finished.
+    pub fn from_dimacs_with_progress<R, F>(
+        reader: R,
+        progress: F,
+    ) -> Result<Self, fio::ParsingError>
+    where
+        R: io::Read,
+        F: FnMut(usize, usize),
+    {
+        Ok(fio::dimacs::parse_cnf_with_progress(reader, progress)?)
+    }
+
     /// Parses an OPB instance from a reader object.
     ///
     /// # File Format
@@ -911,7 +1321,7 @@ impl<VM: ManageVars + Default> SatInstance<VM> {
 
     /// Parses an OPB instance from a file path. For more details see
     /// [`SatInstance::from_opb`]. With feature `compression` supports
-    /// bzip2 and gzip compression, detected by the file extension.
+    /// compressed files, see [`fio`](crate::instances::fio#compressed-files).
     pub fn from_opb_path<P: AsRef<Path>>(
         path: P,
         opts: fio::opb::Options,
@@ -965,3 +1375,225 @@ impl<VM: ManageVars + Default> From<Cnf> for SatInstance<VM> {
         inst
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Cnf, SatInstance};
+    use crate::{
+        clause, lit,
+        solvers::SolverResult,
+        types::{constraints::CardConstraint, Assignment, Clause, TernaryVal},
+        var,
+    };
+
+    #[test]
+    fn add_equiv() {
+        let mut cnf = Cnf::new();
+        cnf.add_equiv(lit![0], !lit![1]);
+        assert_eq!(
+            cnf,
+            Cnf::from_iter([clause![!lit![0], !lit![1]], clause![lit![1], lit![0]]])
+        );
+    }
+
+    #[test]
+    fn cnf_size_and_capacity() {
+        let mut cnf = Cnf::with_capacity(3);
+        assert!(cnf.capacity() >= 3);
+        let mut cl = Clause::with_capacity(2);
+        assert!(cl.capacity() >= 2);
+        cl.add(lit![0]);
+        cl.add(!lit![1]);
+        cnf.add_clause_ref(&cl);
+        cnf.add_unit(lit![2]);
+        assert_eq!(cl, clause![lit![0], !lit![1]]);
+        assert_eq!(cnf.len(), 2);
+        assert_eq!(cnf.n_lits(), 3);
+        assert_eq!(Cnf::new().n_lits(), 0);
+    }
+
+    #[test]
+    fn cnf_is_satisfied() {
+        let cnf = Cnf::from_iter([clause![lit![0], !lit![1]], clause![lit![2]]]);
+        let mut assign =
+            Assignment::from(vec![TernaryVal::False, TernaryVal::False, TernaryVal::True]);
+        assert!(cnf.is_satisfied(&assign));
+        assign.assign_var(var![1], TernaryVal::True);
+        assert!(!cnf.is_satisfied(&assign));
+        // Clauses depending only on unassigned variables are not satisfied
+        assign.assign_var(var![1], TernaryVal::DontCare);
+        assert!(!cnf.is_satisfied(&assign));
+        assert!(Cnf::new().is_satisfied(&Assignment::default()));
+    }
+
+    #[test]
+    fn clause_access() {
+        let cnf = Cnf::from_iter([clause![lit![0], !lit![1]], clause![!lit![2]]]);
+        assert_eq!(cnf.len(), 2);
+        assert!(!cnf.is_empty());
+        assert_eq!(cnf[1], clause![!lit![2]]);
+        let mut n_lits = 0;
+        for cl in &cnf {
+            n_lits += cl.len();
+        }
+        assert_eq!(n_lits, 3);
+        assert!(cnf.iter().eq(&cnf));
+    }
+
+    #[test]
+    fn concatenation() {
+        let first = Cnf::from_iter([clause![lit![0]], clause![lit![1]]]);
+        let second = Cnf::from_iter([clause![lit![2]]]);
+        let expected = Cnf::from_iter([clause![lit![0]], clause![lit![1]], clause![lit![2]]]);
+
+        let mut cnf = first.clone();
+        cnf.extend_from_cnf(second.clone());
+        assert_eq!(cnf, expected);
+
+        let mut cnf = first.clone();
+        cnf += second.clone();
+        assert_eq!(cnf.len(), 3);
+        assert_eq!(cnf, expected);
+
+        assert_eq!(first.join(second), expected);
+    }
+
+    #[test]
+    fn compact_vars() {
+        let mut cnf = Cnf::from_iter([clause![lit![0], !lit![5]], clause![!lit![9], lit![0]]]);
+        let map = cnf.compact_vars();
+        assert_eq!(
+            cnf,
+            Cnf::from_iter([clause![lit![0], !lit![1]], clause![!lit![2], lit![0]]])
+        );
+        assert_eq!(map.len(), 3);
+        assert_eq!(map[&var![0]], var![0]);
+        assert_eq!(map[&var![5]], var![1]);
+        assert_eq!(map[&var![9]], var![2]);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let cnf = Cnf::from_iter([clause![lit![0], !lit![1]], clause![!lit![2]]]);
+        let json = serde_json::to_string(&cnf).unwrap();
+        assert_eq!(json, "[[1,-2],[-3]]");
+        let back: Cnf = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, cnf);
+        assert!(serde_json::from_str::<Cnf>("[[0]]").is_err());
+    }
+
+    #[test]
+    fn dimacs_writer_round_trip() {
+        let data = "p cnf 4 3\n1 -2 0\n2 3 0\n-1 -3 4 0\n";
+        let inst: SatInstance = SatInstance::from_dimacs(data.as_bytes()).unwrap();
+        let (cnf, _) = inst.as_cnf();
+        let mut written = vec![];
+        cnf.to_dimacs_writer(&mut written).unwrap();
+        let written = String::from_utf8(written).unwrap();
+        assert!(written.contains("p cnf 4 3\n"));
+        let reparsed: SatInstance = SatInstance::from_dimacs(written.as_bytes()).unwrap();
+        let (reparsed, _) = reparsed.as_cnf();
+        assert_eq!(reparsed, cnf);
+    }
+
+    #[test]
+    fn dimacs_writer_n_vars() {
+        let mut cnf = Cnf::new();
+        cnf.add_binary(lit![0], !lit![1]);
+        let mut written = vec![];
+        cnf.to_dimacs_writer_with_n_vars(&mut written, 5).unwrap();
+        let written = String::from_utf8(written).unwrap();
+        assert!(written.contains("p cnf 5 1\n"));
+        assert!(written.contains("1 -2 0\n"));
+    }
+
+    #[test]
+    fn trivial_result() {
+        let mut cnf = Cnf::new();
+        assert_eq!(cnf.trivial_result(), Some(SolverResult::Sat));
+        cnf.add_binary(lit![0], lit![1]);
+        cnf.add_unit(!lit![0]);
+        assert_eq!(cnf.trivial_result(), None);
+        cnf.add_clause(Clause::new());
+        assert_eq!(cnf.trivial_result(), Some(SolverResult::Unsat));
+    }
+
+    #[test]
+    fn pure_literal_autarky() {
+        let mut cnf = Cnf::new();
+        cnf.add_binary(lit![0], lit![1]);
+        cnf.add_binary(lit![0], !lit![2]);
+        cnf.add_binary(lit![1], lit![2]);
+        cnf.add_binary(!lit![1], !lit![2]);
+        // `x0` only occurs positively
+        let partial = Assignment::from(vec![TernaryVal::True]);
+        assert!(cnf.is_autarky(&partial));
+        cnf.apply_autarky(&partial);
+        assert_eq!(cnf.len(), 2);
+        assert_eq!(cnf[0], clause![lit![1], lit![2]]);
+        assert_eq!(cnf[1], clause![!lit![1], !lit![2]]);
+    }
+
+    #[test]
+    fn not_autarky() {
+        let mut cnf = Cnf::new();
+        cnf.add_binary(lit![0], lit![1]);
+        cnf.add_binary(!lit![0], lit![2]);
+        let partial = Assignment::from(vec![TernaryVal::True]);
+        assert!(!cnf.is_autarky(&partial));
+        let partial = Assignment::from(vec![TernaryVal::DontCare]);
+        assert!(cnf.is_autarky(&partial));
+    }
+
+    #[test]
+    fn propagate_units() {
+        let mut cnf = Cnf::new();
+        cnf.add_unit(lit![0]);
+        cnf.add_binary(!lit![0], lit![1]);
+        cnf.add_ternary(!lit![1], lit![2], lit![3]);
+        cnf.add_binary(lit![0], lit![4]);
+        assert_eq!(cnf.propagate_units(), Some(vec![lit![0], lit![1]]));
+        assert_eq!(cnf.len(), 1);
+        assert_eq!(cnf[0], clause![lit![2], lit![3]]);
+        assert_eq!(cnf.propagate_units(), Some(vec![]));
+    }
+
+    #[test]
+    fn propagate_units_conflict() {
+        let mut cnf = Cnf::new();
+        cnf.add_unit(lit![0]);
+        cnf.add_binary(!lit![0], lit![1]);
+        cnf.add_binary(!lit![1], lit![2]);
+        cnf.add_binary(!lit![0], !lit![2]);
+        assert_eq!(cnf.propagate_units(), None);
+        assert_eq!(cnf.trivial_result(), Some(SolverResult::Unsat));
+    }
+
+    #[test]
+    fn canonical_hash_order_preserving_renaming() {
+        let mut inst1: SatInstance = SatInstance::new();
+        inst1.add_binary(lit![0], !lit![1]);
+        inst1.add_ternary(lit![1], lit![2], !lit![0]);
+        inst1.add_card_constr(CardConstraint::new_ub(vec![lit![0], lit![2]], 1));
+        // Same instance with variables renamed preserving their order,
+        // constraints reordered and a duplicate clause
+        let mut inst2: SatInstance = SatInstance::new();
+        inst2.add_clause(clause![lit![20], !lit![3], lit![5]]);
+        inst2.add_card_constr(CardConstraint::new_ub(vec![lit![20], lit![3]], 1));
+        inst2.add_binary(!lit![5], lit![3]);
+        inst2.add_binary(lit![3], !lit![5]);
+        assert_eq!(inst1.canonical_hash(), inst2.canonical_hash());
+        let mut inst3: SatInstance = SatInstance::new();
+        inst3.add_binary(lit![0], lit![1]);
+        inst3.add_ternary(lit![1], lit![2], !lit![0]);
+        inst3.add_card_constr(CardConstraint::new_ub(vec![lit![0], lit![2]], 1));
+        assert_ne!(inst1.canonical_hash(), inst3.canonical_hash());
+        // Swapping variables 0 and 1 does not preserve their order
+        let mut inst4: SatInstance = SatInstance::new();
+        inst4.add_binary(lit![1], !lit![0]);
+        inst4.add_ternary(lit![0], lit![2], !lit![1]);
+        inst4.add_card_constr(CardConstraint::new_ub(vec![lit![1], lit![2]], 1));
+        assert_ne!(inst1.canonical_hash(), inst4.canonical_hash());
+    }
+}
@@ -54,6 +54,7 @@
 //! | `compression` | Enable parsing and writing compressed input. |
 //! | `bench` | Enable benchmark tests. Behind feature flag since it requires unstable Rust. |
 //! | `rand` | Enable randomization features. (Shuffling clauses etc.) |
+//! | `serde` | Enable `serde` serialization of literals, clauses, CNFs and solver statistics. |
 //!
 //! ## Examples
 //!
@@ -79,6 +80,9 @@ pub mod pyapi;
 
 pub mod utils;
 
+#[cfg(test)]
+mod test_utils;
+
 #[cfg(feature = "bench")]
 #[cfg(test)]
 mod bench;
@@ -106,10 +106,10 @@ use crate::{
     encodings::CollectClauses,
     instances::Cnf,
     lit,
-    types::{Assignment, Clause, Lit, TernaryVal, Var},
+    types::{Assignment, Clause, Lit, RsHashSet, TernaryVal, Var},
 };
 use core::time::Duration;
-use std::fmt;
+use std::{fmt, path::Path};
 
 #[cfg(feature = "ipasir")]
 mod ipasir;
@@ -117,6 +117,40 @@ mod ipasir;
 pub use ipasir::IpasirSolver;
 use thiserror::Error;
 
+mod stack;
+pub use stack::ClauseStack;
+
+mod removable;
+pub use removable::RemovableClauses;
+
+mod session;
+pub use session::{QueryResult, QuerySession};
+
+mod record;
+pub use record::{ClauseRecorder, RecordedClause};
+
+mod verify;
+pub use verify::{verify_result, VerificationError};
+
+mod enumerate;
+pub use enumerate::ModelIterator;
+
+mod backbone;
+pub use backbone::compute_backbone;
+
+mod minimize;
+pub use minimize::{minimize_core, CoreMinimization};
+
+mod race;
+
+mod portfolio;
+pub use portfolio::Portfolio;
+
+#[cfg(feature = "rand")]
+mod diverse;
+#[cfg(feature = "rand")]
+pub use diverse::DiversePortfolio;
+
 /// Trait for all SAT solvers in this library.
 /// Solvers outside of this library can also implement this trait to be able to
 /// use them with this library.
@@ -142,10 +176,13 @@ pub trait Solve: Extend<Clause> {
     /// ```
     fn solve(&mut self) -> Result<SolverResult, SolverError>;
     /// Gets a solution found by the solver up to a specified highest variable.
+    /// The default implementation queries [`Solve::lit_val`] for every
+    /// variable; solvers that can read the model in bulk can override this.
     ///
     /// # Errors
     ///
-    /// - If the solver is not in the satisfied state
+    /// - If the solver is not in the satisfied state, the same
+    ///   [`SolverError::State`] as from [`Solve::lit_val`]
     /// - A specific implementation might return other errors
     fn solution(&self, high_var: Var) -> Result<Assignment, SolverError> {
         let mut assignment = Vec::new();
@@ -215,10 +252,103 @@ pub trait Solve: Extend<Clause> {
     fn add_ternary(&mut self, lit1: Lit, lit2: Lit, lit3: Lit) -> SolveMightFail {
         self.add_clause(clause![lit1, lit2, lit3])
     }
+    /// Adds all clauses from an iterator. The default implementation adds the
+    /// clauses one by one, solvers that can add clauses in bulk may override
+    /// this.
+    fn add_clauses<I>(&mut self, clauses: I) -> SolveMightFail
+    where
+        I: IntoIterator<Item = Clause>,
+        Self: Sized,
+    {
+        clauses.into_iter().try_for_each(|cl| self.add_clause(cl))
+    }
     /// Adds all clauses from a [`Cnf`] instance.
     fn add_cnf(&mut self, cnf: Cnf) -> SolveMightFail {
         cnf.into_iter().try_for_each(|cl| self.add_clause(cl))
     }
+    /// Enables or disables detection of duplicate clauses. While enabled, the
+    /// solver keeps all added clauses in normalized form and warns (via the
+    /// `logging` feature) when a clause is added that was added before.
+    /// Duplicate clauses are still added to the solver. The number of
+    /// detected duplicates is available via
+    /// [`SolveStats::n_duplicate_clauses`].
+    ///
+    /// # Errors
+    ///
+    /// [`SolverError::NoTypeSupport`] if the solver does not support detecting
+    /// duplicate clauses
+    fn warn_duplicate_clauses(&mut self, _on: bool) -> SolveMightFail {
+        Err(SolverError::NoTypeSupport(
+            self.signature(),
+            "detecting duplicate clauses",
+        ))
+    }
+    /// Solves the internal CNF formula and calls `cb` with the current
+    /// statistics every `interval_conflicts` conflicts. The search is split
+    /// into chunks with [`LimitConflicts::limit_conflicts`] and continued with
+    /// [`LimitConflicts::resume`], so learned clauses are kept between chunks.
+    /// If the solver is interrupted before a chunk used up its conflict
+    /// budget, e.g., through an [`Interrupt::interrupter`], solving stops and
+    /// [`SolverResult::Interrupted`] is returned.
+    ///
+    /// Every chunk counts as a solver call in the statistics. The conflict
+    /// limit is removed before returning, also if solving fails.
+    ///
+    /// # Errors
+    ///
+    /// - [`SolverError::Api`] if `interval_conflicts` is zero
+    /// - Any error returned by the solver
+    fn solve_with_progress<CB>(
+        &mut self,
+        interval_conflicts: u64,
+        mut cb: CB,
+    ) -> Result<SolverResult, SolverError>
+    where
+        CB: FnMut(&SolverStats),
+        Self: LimitConflicts + GetInternalStats + SolveStats + Sized,
+    {
+        if interval_conflicts == 0 {
+            return Err(SolverError::Api(
+                "progress interval must be at least one conflict".to_string(),
+            ));
+        }
+        let res = solve_in_chunks(self, interval_conflicts, &mut cb);
+        self.limit_conflicts(None)?;
+        res
+    }
+}
+
+/// Solves in conflict-limited chunks for [`Solve::solve_with_progress`],
+/// leaving the last conflict limit in place
+fn solve_in_chunks<S, CB>(
+    solver: &mut S,
+    interval_conflicts: u64,
+    cb: &mut CB,
+) -> Result<SolverResult, SolverError>
+where
+    CB: FnMut(&SolverStats),
+    S: Solve + LimitConflicts + GetInternalStats + SolveStats,
+{
+    let chunk = u32::try_from(interval_conflicts).unwrap_or(u32::MAX);
+    let mut last_report = solver.conflicts();
+    let mut chunk_start = last_report;
+    solver.limit_conflicts(Some(chunk))?;
+    let mut res = solver.solve()?;
+    while res == SolverResult::Interrupted {
+        let conflicts = solver.conflicts();
+        if conflicts - chunk_start < chunk as usize {
+            // Interrupted for another reason than the conflict budget
+            return Ok(res);
+        }
+        if (conflicts - last_report) as u64 >= interval_conflicts {
+            cb(&solver.stats());
+            last_report = conflicts;
+        }
+        chunk_start = conflicts;
+        solver.limit_conflicts(Some(chunk))?;
+        res = solver.resume()?;
+    }
+    Ok(res)
 }
 
 /// Trait for all SAT solvers in this library.
@@ -231,6 +361,43 @@ pub trait SolveIncremental: Solve {
     /// A core is a clause entailed by the formula that contains only inverted
     /// literals of the assumptions.
     fn core(&mut self) -> Result<Vec<Lit>, SolverError>;
+    /// Determines which of the assumptions of a satisfiable query were
+    /// necessary. Must be called after [`SolveIncremental::solve_assumps`]
+    /// returned [`SolverResult::Sat`] for `assumps`. An assumption is dropped
+    /// if it is entailed by the formula and the other remaining assumptions,
+    /// which is checked by solving with the assumption negated. Afterwards,
+    /// the solver is left in a satisfied state under the returned assumptions.
+    /// Unlike for [`SolveIncremental::core`], the solver does not store the
+    /// assumptions, so they need to be passed again. If `assumps` are not
+    /// satisfiable together, a state error is returned, and if a solver call
+    /// is interrupted, [`SolverError::Interrupted`] is returned.
+    fn relevant_assumptions(&mut self, assumps: &[Lit]) -> Result<Vec<Lit>, SolverError> {
+        // Fails with a state error if the solver is not in a satisfied state
+        if let Some(&a) = assumps.first() {
+            self.lit_val(a)?;
+        }
+        let mut relevant = Vec::with_capacity(assumps.len());
+        for (idx, &a) in assumps.iter().enumerate() {
+            let mut check = relevant.clone();
+            check.extend_from_slice(&assumps[idx + 1..]);
+            check.push(!a);
+            if self.solve_assumps(&check)? != SolverResult::Unsat {
+                relevant.push(a);
+            }
+        }
+        match self.solve_assumps(&relevant)? {
+            SolverResult::Sat => Ok(relevant),
+            SolverResult::Unsat => Err(SolverError::State(SolverState::Unsat, SolverState::Sat)),
+            SolverResult::Interrupted => Err(SolverError::Interrupted),
+        }
+    }
+    /// Enumerates the models of the solver projected onto `vars`. After each
+    /// model, a blocking clause over the assigned variables of the projection
+    /// is added to the solver, so the solver permanently excludes all
+    /// enumerated models.
+    fn enumerate(&mut self, vars: &[Var]) -> ModelIterator<'_, Self> {
+        ModelIterator::new(self, vars)
+    }
 }
 
 /// Trait for all solvers that can be terminated by a termination callback.
@@ -257,11 +424,81 @@ pub trait Learn<'learn> {
     fn detach_learner(&mut self);
 }
 
+/// Trait for all solvers that can log a DRAT proof of unsatisfiability
+pub trait ProofLogging {
+    /// Starts writing a proof in DRAT text format to the file at `path`. The
+    /// proof contains all learned and deleted clauses and ends with the empty
+    /// clause if the solver returns [`SolverResult::Unsat`].
+    ///
+    /// Only a path is supported as the proof sink, not an arbitrary
+    /// [`std::io::Write`]r. The solvers' C APIs write proofs directly to a C
+    /// `FILE` handle, so the proof cannot be passed through a Rust writer. To
+    /// process the proof while solving, pass the path of a named pipe.
+    ///
+    /// # Errors
+    ///
+    /// - [`SolverError::State`] if the solver is not in state
+    ///   [`SolverState::Configuring`], i.e., if clauses have been added or the
+    ///   solver has been called already
+    /// - [`SolverError::Api`] if the proof file cannot be opened
+    fn enable_proof<P: AsRef<Path>>(&mut self, path: P) -> Result<(), SolverError>;
+    /// Flushes and closes the proof file. Proof logging cannot be enabled
+    /// again afterwards.
+    fn close_proof(&mut self) -> Result<(), SolverError>;
+    /// Writes a comment to the proof. Every line of `comment` is written as a
+    /// separate comment line. This is used, e.g., by [`ClauseRecorder`] to
+    /// note the origins of added clauses in the proof.
+    ///
+    /// # Errors
+    ///
+    /// - [`SolverError::Api`] if proof logging is not enabled or the solver
+    ///   does not support writing comments to the proof
+    fn proof_comment(&mut self, _comment: &str) -> Result<(), SolverError> {
+        Err(SolverError::Api(
+            "writing comments to the proof is not supported".to_string(),
+        ))
+    }
+}
+
 /// Trait for all solvers that can be asynchronously interrupt.
 pub trait Interrupt {
     type Interrupter: InterruptSolver + Send + 'static;
     /// Gets a thread safe interrupter object that can be used to terminate the solver
     fn interrupter(&mut self) -> Self::Interrupter;
+    /// Solves the internal CNF formula with a wall-clock deadline. A watchdog
+    /// thread interrupts the solver once `timeout` has elapsed, in which case
+    /// [`SolverResult::Interrupted`] is returned. The watchdog is stopped and
+    /// joined as soon as the solver returns and never interrupts the solver
+    /// after that, so it cannot terminate a later solver call. If the deadline
+    /// passes after the solver last checked for interrupts, the solver's
+    /// actual result is returned.
+    fn solve_timeout(&mut self, timeout: Duration) -> Result<SolverResult, SolverError>
+    where
+        Self: Solve + Sized,
+    {
+        let interrupter = self.interrupter();
+        let finished = std::sync::Arc::new(std::sync::Mutex::new(false));
+        let (stop, stopped) = std::sync::mpsc::channel::<()>();
+        let watchdog = {
+            let finished = finished.clone();
+            std::thread::spawn(move || {
+                if let Err(std::sync::mpsc::RecvTimeoutError::Timeout) =
+                    stopped.recv_timeout(timeout)
+                {
+                    // Only interrupt while the solver call is still running
+                    let finished = finished.lock().expect("watchdog lock poisoned");
+                    if !*finished {
+                        interrupter.interrupt();
+                    }
+                }
+            })
+        };
+        let res = self.solve();
+        *finished.lock().expect("watchdog lock poisoned") = true;
+        drop(stop);
+        watchdog.join().expect("watchdog thread panicked");
+        res
+    }
 }
 
 /// A thread safe interrupter for a solver
@@ -280,6 +517,25 @@ pub trait PhaseLit {
     fn unphase_lit(&mut self, lit: Lit) -> Result<(), SolverError> {
         self.unphase_var(lit.var())
     }
+    /// Forces the default decision phases of all variables assigned in an
+    /// assignment, e.g., to warm-start a solver from a previous solution.
+    /// Variables that are [`TernaryVal::DontCare`] are not phased. The default
+    /// implementation calls [`PhaseLit::phase_lit`] for every assigned
+    /// variable.
+    fn phase_assignment(&mut self, assignment: &Assignment) -> Result<(), SolverError> {
+        let Some(max_var) = assignment.max_var() else {
+            return Ok(());
+        };
+        for idx in 0..=max_var.idx32() {
+            let var = Var::new(idx);
+            match assignment.var_value(var) {
+                TernaryVal::True => self.phase_lit(var.pos_lit())?,
+                TernaryVal::False => self.phase_lit(var.neg_lit())?,
+                TernaryVal::DontCare => (),
+            }
+        }
+        Ok(())
+    }
 }
 
 /// Trait for freezing and melting variables in solvers with pre-/inprocessing.
@@ -300,10 +556,75 @@ pub trait FlipLit {
     fn is_flippable(&mut self, lit: Lit) -> Result<bool, SolverError>;
 }
 
+/// Trait for solvers that can report the literals implied by unit propagation
+/// from a set of assumptions without solving.
+///
+/// None of the solver backends implement this trait, since their C APIs do
+/// not expose unit propagation. [`ClauseRecorder`] answers propagation queries
+/// on the clauses it recorded.
+pub trait PropagateQuery: Solve {
+    /// Propagates the assumptions and returns the literals entailed by unit
+    /// propagation, including the assumptions themselves, and whether
+    /// propagation resulted in a conflict
+    ///
+    /// # Errors
+    ///
+    /// If propagation fails in the solver
+    fn propagate(&mut self, assumps: Vec<Lit>) -> Result<(Vec<Lit>, bool), SolverError>;
+}
+
+/// Trait for all solvers that can be reset to an empty formula without
+/// creating a new solver object. None of the supported solvers can delete
+/// clauses through their C API, so resetting releases the underlying solver
+/// and initializes a new one. Afterwards, the solver is in the same state as
+/// a freshly created solver: all clauses, learned clauses, statistics,
+/// options and limits are discarded.
+///
+/// Interrupters obtained through [`Interrupt::interrupter`] before the reset
+/// refer to the released solver and must not be used anymore.
+pub trait Reset: Solve {
+    /// Resets the solver to an empty formula
+    ///
+    /// # Errors
+    ///
+    /// If the solver could not be reset
+    fn reset(&mut self) -> Result<(), SolverError>;
+}
+
 /// Trait for all solvers that can limit the number of conflicts
 pub trait LimitConflicts {
     /// Sets or removes a limit on the number of conflicts
     fn limit_conflicts(&mut self, limit: Option<u32>) -> Result<(), SolverError>;
+    /// Continues solving after a call returned [`SolverResult::Interrupted`]
+    /// because of the conflict limit. The solver state, including learned
+    /// clauses, is kept between calls, so solving in budgeted chunks makes
+    /// progress rather than starting over.
+    ///
+    /// A conflict limit is consumed by the call that hits it. Set a new
+    /// budget with [`LimitConflicts::limit_conflicts`] before resuming,
+    /// otherwise the call is not limited or is interrupted immediately,
+    /// depending on the solver.
+    ///
+    /// This only continues a call to [`Solve::solve`]. Assumptions are not
+    /// kept between calls, so use [`LimitConflicts::resume_assumps`] to
+    /// continue a call to [`SolveIncremental::solve_assumps`].
+    fn resume(&mut self) -> Result<SolverResult, SolverError>
+    where
+        Self: Solve,
+    {
+        self.solve()
+    }
+    /// Continues solving under the assumptions `assumps` after a call to
+    /// [`SolveIncremental::solve_assumps`] returned
+    /// [`SolverResult::Interrupted`] because of the conflict limit. Pass the
+    /// same assumptions as in the interrupted call. Otherwise behaves like
+    /// [`LimitConflicts::resume`].
+    fn resume_assumps(&mut self, assumps: &[Lit]) -> Result<SolverResult, SolverError>
+    where
+        Self: SolveIncremental,
+    {
+        self.solve_assumps(assumps)
+    }
 }
 
 /// Trait for all solvers that can limit the number of decisions
@@ -318,6 +639,40 @@ pub trait LimitPropagations {
     fn limit_propagations(&mut self, limit: Option<u32>) -> Result<(), SolverError>;
 }
 
+/// Trait for all solvers that allow fixing their random seed for reproducible
+/// runs. Implemented by CaDiCaL and Kissat. Minisat and Glucose have a random
+/// seed internally, but their C APIs do not expose it.
+pub trait SetSeed {
+    /// Sets the seed of the solver's random number generator
+    ///
+    /// # Errors
+    ///
+    /// - [`SolverError::State`] if the solver has been used already
+    /// - [`SolverError::Api`] if the seed is out of the solver's range
+    fn set_seed(&mut self, seed: u64) -> Result<(), SolverError>;
+}
+
+/// Trait for configuring the number of threads a solver uses. Single-threaded
+/// solvers can implement this trait with the default implementation, which only
+/// accepts a single thread.
+pub trait SetThreads: Solve {
+    /// Sets the number of threads the solver uses for solving
+    ///
+    /// # Errors
+    ///
+    /// [`SolverError::NoTypeSupport`] if the solver does not support using
+    /// `n_threads` threads
+    fn set_threads(&mut self, n_threads: usize) -> SolveMightFail {
+        if n_threads == 1 {
+            return Ok(());
+        }
+        Err(SolverError::NoTypeSupport(
+            self.signature(),
+            "multiple threads",
+        ))
+    }
+}
+
 /// Trait for all solvers allowing access to internal search statistics
 pub trait GetInternalStats {
     /// Gets the number of propagations
@@ -328,6 +683,12 @@ pub trait GetInternalStats {
     fn conflicts(&self) -> usize;
 }
 
+/// Trait for all solvers allowing access to their trail
+pub trait GetTrail {
+    /// Gets the number of literals currently assigned on the trail
+    fn trail_len(&self) -> usize;
+}
+
 /// Return type of solver calls that don't return but might fail
 pub type SolveMightFail = Result<(), SolverError>;
 
@@ -342,8 +703,10 @@ type OptTermCallbackStore<'a> = Option<Box<TermCallbackPtr<'a>>>;
 /// Double boxing is necessary to get thin pointers for casting
 type OptLearnCallbackStore<'a> = Option<Box<LearnCallbackPtr<'a>>>;
 
-/// Solver statistics
+/// Solver statistics. New statistics might be added in the future, create
+/// instances via [`Default`] and set the fields.
 #[derive(Clone, PartialEq, Default)]
+#[non_exhaustive]
 pub struct SolverStats {
     /// The number of satisfiable queries executed
     pub n_sat: usize,
@@ -359,6 +722,37 @@ pub struct SolverStats {
     pub avg_clause_len: f32,
     /// The total CPU time spent solving
     pub cpu_solve_time: Duration,
+    /// The number of duplicate clauses detected, see
+    /// [`Solve::warn_duplicate_clauses`]
+    pub n_duplicate_clauses: usize,
+}
+
+impl SolverStats {
+    /// Serializes the statistics to a JSON object
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("solver statistics are always serializable")
+    }
+}
+
+/// Serializes the statistics with the CPU solve time additionally given in
+/// seconds as `cpu_solve_time_secs`
+#[cfg(feature = "serde")]
+impl serde::Serialize for SolverStats {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+        let mut state = serializer.serialize_struct("SolverStats", 9)?;
+        state.serialize_field("n_sat", &self.n_sat)?;
+        state.serialize_field("n_unsat", &self.n_unsat)?;
+        state.serialize_field("n_terminated", &self.n_terminated)?;
+        state.serialize_field("n_clauses", &self.n_clauses)?;
+        state.serialize_field("max_var", &self.max_var)?;
+        state.serialize_field("avg_clause_len", &self.avg_clause_len)?;
+        state.serialize_field("cpu_solve_time", &self.cpu_solve_time)?;
+        state.serialize_field("cpu_solve_time_secs", &self.cpu_solve_time.as_secs_f64())?;
+        state.serialize_field("n_duplicate_clauses", &self.n_duplicate_clauses)?;
+        state.end()
+    }
 }
 
 /// Trait for solvers that track certain statistics.
@@ -407,6 +801,56 @@ pub trait SolveStats {
     fn cpu_solve_time(&self) -> Duration {
         self.stats().cpu_solve_time
     }
+    /// Gets the number of duplicate clauses detected while
+    /// [`Solve::warn_duplicate_clauses`] was enabled.
+    fn n_duplicate_clauses(&self) -> usize {
+        self.stats().n_duplicate_clauses
+    }
+}
+
+/// Tracker for detecting duplicate clauses added to a solver. This is intended
+/// to be used by solver implementations for implementing
+/// [`Solve::warn_duplicate_clauses`].
+#[derive(Debug, Default, Clone)]
+pub struct DuplicateClauseTracker {
+    /// The normalized clauses seen so far, if tracking is enabled
+    seen: Option<RsHashSet<Clause>>,
+}
+
+impl DuplicateClauseTracker {
+    /// Enables or disables the tracker. Disabling the tracker forgets all
+    /// clauses seen so far.
+    pub fn set_active(&mut self, on: bool) {
+        match (on, &self.seen) {
+            (true, None) => self.seen = Some(RsHashSet::default()),
+            (false, Some(_)) => self.seen = None,
+            _ => (),
+        }
+    }
+
+    /// Checks whether the tracker is enabled
+    pub fn is_active(&self) -> bool {
+        self.seen.is_some()
+    }
+
+    /// Records a clause that is being added. Returns `true` and emits a
+    /// warning if the clause is a duplicate of a previously added clause.
+    pub fn track(&mut self, clause: &Clause) -> bool {
+        let Some(seen) = &mut self.seen else {
+            return false;
+        };
+        let Some(norm) = clause.clone().normalize() else {
+            // Tautologies are not tracked
+            return false;
+        };
+        if seen.contains(&norm) {
+            #[cfg(feature = "logging")]
+            log::warn!("duplicate clause added to solver: {norm}");
+            return true;
+        }
+        seen.insert(norm);
+        false
+    }
 }
 
 /// States that the solver can be in.
@@ -476,6 +920,14 @@ pub enum SolverError {
     /// The solver was expected to be in the second [`SolverState`], but it is in the first.
     #[error("solvers needs to be in state {1} but was in state {0}")]
     State(SolverState, SolverState),
+    /// A solver call was interrupted while an algorithm required a definite
+    /// result
+    #[error("solver call was interrupted")]
+    Interrupted,
+    /// The solver (first) does not support the requested functionality
+    /// (second)
+    #[error("{0} does not support {1}")]
+    NoTypeSupport(&'static str, &'static str),
 }
 
 impl<S: Solve + SolveStats> CollectClauses for S {
@@ -483,3 +935,52 @@ impl<S: Solve + SolveStats> CollectClauses for S {
         self.n_clauses()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::DuplicateClauseTracker;
+    use crate::{clause, lit};
+
+    #[test]
+    fn duplicate_clause_tracker() {
+        let mut tracker = DuplicateClauseTracker::default();
+        assert!(!tracker.track(&clause![lit![0], lit![1]]));
+        tracker.set_active(true);
+        assert!(!tracker.track(&clause![lit![0], lit![1]]));
+        assert!(tracker.track(&clause![lit![1], lit![0]]));
+        assert!(tracker.track(&clause![lit![1], lit![0], lit![0]]));
+        assert!(!tracker.track(&clause![lit![1], !lit![0]]));
+        tracker.set_active(false);
+        assert!(!tracker.track(&clause![lit![1], lit![0]]));
+    }
+
+    #[test]
+    fn phase_assignment_skips_dont_care() {
+        use super::{PhaseLit, SolverError};
+        use crate::types::{Assignment, Lit, TernaryVal, Var};
+
+        #[derive(Default)]
+        struct PhaseRecorder(Vec<Lit>);
+        impl PhaseLit for PhaseRecorder {
+            fn phase_lit(&mut self, lit: Lit) -> Result<(), SolverError> {
+                self.0.push(lit);
+                Ok(())
+            }
+            fn unphase_var(&mut self, _var: Var) -> Result<(), SolverError> {
+                Ok(())
+            }
+        }
+
+        let assignment = Assignment::from(vec![
+            TernaryVal::True,
+            TernaryVal::DontCare,
+            TernaryVal::False,
+        ]);
+        let mut recorder = PhaseRecorder::default();
+        recorder.phase_assignment(&assignment).unwrap();
+        assert_eq!(recorder.0, vec![lit![0], !lit![2]]);
+        let mut recorder = PhaseRecorder::default();
+        recorder.phase_assignment(&Assignment::default()).unwrap();
+        assert!(recorder.0.is_empty());
+    }
+}
@@ -0,0 +1,56 @@
+//! # Backbone Computation
+//!
+//! The backbone of a satisfiable formula is the set of literals that are true
+//! in all of its models.
+
+use crate::types::{Lit, TernaryVal};
+
+use super::{SolveIncremental, SolverError, SolverResult};
+
+/// Computes the literals among `candidates` that are true in all models of the
+/// solver, using iterative SAT calls. Candidates that are not true in the
+/// current model are pruned immediately. Each remaining candidate is checked
+/// by solving under its negation. If that is satisfiable, all candidates that
+/// are not true in the new model are pruned as well, so in many cases fewer
+/// solver calls than candidates are needed. The backbone literals are
+/// returned in the order of `candidates`.
+///
+/// The solver must be in a satisfied state when calling this function, its
+/// current model is used as the initial model. Afterwards, the solver can be
+/// in any state.
+///
+/// # Errors
+///
+/// - [`SolverError::State`] if the solver is not in a satisfied state
+/// - [`SolverError::Interrupted`] if a solver call is interrupted
+/// - Any error returned by the solver
+pub fn compute_backbone<S: SolveIncremental>(
+    solver: &mut S,
+    candidates: &[Lit],
+) -> Result<Vec<Lit>, SolverError> {
+    let mut backbone = Vec::with_capacity(candidates.len());
+    for &lit in candidates {
+        if solver.lit_val(lit)? == TernaryVal::True {
+            backbone.push(lit);
+        }
+    }
+    let mut idx = 0;
+    while idx < backbone.len() {
+        match solver.solve_assumps(&[!backbone[idx]])? {
+            SolverResult::Unsat => idx += 1,
+            SolverResult::Sat => {
+                // The candidate is not in the backbone, prune with the new model
+                let mut pruned = Vec::with_capacity(backbone.len() - 1);
+                pruned.extend_from_slice(&backbone[..idx]);
+                for &lit in &backbone[idx + 1..] {
+                    if solver.lit_val(lit)? == TernaryVal::True {
+                        pruned.push(lit);
+                    }
+                }
+                backbone = pruned;
+            }
+            SolverResult::Interrupted => return Err(SolverError::Interrupted),
+        }
+    }
+    Ok(backbone)
+}
@@ -0,0 +1,125 @@
+//! # Portfolio of Transformed Instance Variants
+//!
+//! Solvers can be sensitive to the order of variables and clauses. A
+//! [`DiversePortfolio`] solves several equivalent, randomly transformed
+//! variants of an instance in parallel and takes the first result.
+
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+
+use super::{race::race, Interrupt, Solve, SolverError, SolverResult};
+use crate::{
+    instances::{Cnf, ManageVars, SatInstance},
+    types::{Assignment, Clause, Lit, TernaryVal, Var},
+};
+
+/// A variant of the instance with shuffled variables, flipped polarities and
+/// shuffled clauses
+struct Variant {
+    /// The transformed clauses
+    cnf: Cnf,
+    /// For each original variable, the literal in the variant representing its
+    /// positive literal
+    lit_map: Vec<Lit>,
+}
+
+impl Variant {
+    /// Generates a random variant of a CNF over `n_vars` variables
+    fn new(cnf: &Cnf, n_vars: u32, rng: &mut StdRng) -> Self {
+        let mut perm: Vec<u32> = (0..n_vars).collect();
+        perm.shuffle(rng);
+        let lit_map: Vec<Lit> = perm
+            .into_iter()
+            .map(|idx| Lit::new(idx, rng.gen()))
+            .collect();
+        let mut clauses: Vec<Clause> = cnf
+            .iter()
+            .map(|cl| {
+                cl.iter()
+                    .map(|&l| {
+                        let mapped = lit_map[l.var().idx()];
+                        if l.is_neg() {
+                            !mapped
+                        } else {
+                            mapped
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+        clauses.shuffle(rng);
+        Self {
+            cnf: Cnf::from_iter(clauses),
+            lit_map,
+        }
+    }
+
+    /// Maps a model of the variant back to the original variables
+    fn map_back(&self, model: &Assignment) -> Assignment {
+        Assignment::from(
+            self.lit_map
+                .iter()
+                .map(|&l| model.lit_value(l))
+                .collect::<Vec<TernaryVal>>(),
+        )
+    }
+}
+
+/// A portfolio of equivalent variants of an instance. Each variant has
+/// randomly permuted variables, randomly flipped polarities and a random
+/// clause order. The variants are generated deterministically from a seed.
+pub struct DiversePortfolio {
+    /// The generated variants
+    variants: Vec<Variant>,
+    /// The number of variables in the original instance
+    n_vars: u32,
+}
+
+impl DiversePortfolio {
+    /// Generates `n_variants` variants of an instance. Cardinality and
+    /// pseudo-boolean constraints are encoded to CNF first.
+    pub fn from_instance<VM: ManageVars>(
+        instance: SatInstance<VM>,
+        n_variants: usize,
+        seed: u64,
+    ) -> Self {
+        let (cnf, var_manager) = instance.as_cnf();
+        let n_vars = var_manager.n_used();
+        let mut rng = StdRng::seed_from_u64(seed);
+        let variants = (0..n_variants)
+            .map(|_| Variant::new(&cnf, n_vars, &mut rng))
+            .collect();
+        Self { variants, n_vars }
+    }
+
+    /// Gets the number of variants in the portfolio
+    pub fn n_variants(&self) -> usize {
+        self.variants.len()
+    }
+
+    /// Races solvers created by `solver_factory` on all variants in parallel.
+    /// The first definite result is returned and all other solvers are
+    /// interrupted. If the result is satisfiable, the model is mapped back to
+    /// the variables of the original instance. If all solvers were
+    /// interrupted, [`SolverResult::Interrupted`] is returned. A panicking
+    /// solver only drops out of the race.
+    pub fn solve<S, F>(
+        &self,
+        solver_factory: F,
+    ) -> Result<(SolverResult, Option<Assignment>), SolverError>
+    where
+        S: Solve + Interrupt,
+        F: Fn() -> S + Sync,
+    {
+        race(self.variants.len(), |idx, race| {
+            let variant = &self.variants[idx];
+            let mut solver = solver_factory();
+            solver.add_cnf(variant.cnf.clone())?;
+            race.run(idx, solver, |solver| {
+                if self.n_vars == 0 {
+                    return Ok(Assignment::default());
+                }
+                Ok(variant.map_back(&solver.solution(Var::new(self.n_vars - 1))?))
+            })
+        })
+    }
+}
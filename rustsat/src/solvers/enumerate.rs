@@ -0,0 +1,79 @@
+//! # Model Enumeration
+//!
+//! Enumeration of all models of a solver projected onto a set of variables.
+//! After every model, a blocking clause excluding the projected model is added
+//! to the solver.
+
+use crate::types::{Assignment, Var};
+
+use super::{SolveIncremental, SolverError, SolverResult};
+
+/// Iterator over the models of a solver projected onto a set of variables.
+/// Created by [`SolveIncremental::enumerate`].
+///
+/// Each item is a model in which all variables outside of the projection are
+/// [`crate::types::TernaryVal::DontCare`]. The iterator ends when the solver
+/// returns [`SolverResult::Unsat`]. If the solver is interrupted, e.g., by a
+/// limit, [`SolverError::Interrupted`] is returned as the last item, so an
+/// incomplete enumeration cannot be mistaken for a complete one. If the solver
+/// fails, the error is returned as the last item as well.
+pub struct ModelIterator<'solver, S: ?Sized> {
+    /// The solver to enumerate models of
+    solver: &'solver mut S,
+    /// The variables to project the models onto
+    vars: Vec<Var>,
+    /// Whether the enumeration is done
+    done: bool,
+}
+
+impl<'solver, S: SolveIncremental + ?Sized> ModelIterator<'solver, S> {
+    /// Creates a new model iterator
+    pub(super) fn new(solver: &'solver mut S, vars: &[Var]) -> Self {
+        ModelIterator {
+            solver,
+            vars: vars.to_vec(),
+            done: false,
+        }
+    }
+
+    /// Finds the next projected model and blocks it
+    fn next_model(&mut self) -> Result<Option<Assignment>, SolverError> {
+        match self.solver.solve()? {
+            SolverResult::Sat => (),
+            SolverResult::Unsat => return Ok(None),
+            SolverResult::Interrupted => return Err(SolverError::Interrupted),
+        }
+        // Variables without a value are not blocked, otherwise near-identical
+        // models would be enumerated
+        let model = Assignment::from_solver(self.solver, self.vars.iter().copied())?;
+        let blocking = model.as_clause();
+        if blocking.is_empty() {
+            // All further models have the same projection
+            self.done = true;
+        } else {
+            self.solver.add_clause(blocking)?;
+        }
+        Ok(Some(model))
+    }
+}
+
+impl<S: SolveIncremental + ?Sized> Iterator for ModelIterator<'_, S> {
+    type Item = Result<Assignment, SolverError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        match self.next_model() {
+            Ok(Some(model)) => Some(Ok(model)),
+            Ok(None) => {
+                self.done = true;
+                None
+            }
+            Err(err) => {
+                self.done = true;
+                Some(Err(err))
+            }
+        }
+    }
+}
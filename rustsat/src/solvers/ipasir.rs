@@ -6,8 +6,9 @@
 use core::ffi::{c_int, c_void, CStr};
 
 use super::{
-    ControlSignal, Learn, OptLearnCallbackStore, OptTermCallbackStore, Solve, SolveIncremental,
-    SolveMightFail, SolveStats, SolverError, SolverResult, SolverState, SolverStats, Terminate,
+    ControlSignal, DuplicateClauseTracker, Learn, OptLearnCallbackStore, OptTermCallbackStore,
+    Solve, SolveIncremental, SolveMightFail, SolveStats, SolverError, SolverResult, SolverState,
+    SolverStats, Terminate,
 };
 use crate::types::{Clause, Lit, TernaryVal};
 use cpu_time::ProcessTime;
@@ -44,6 +45,7 @@ pub struct IpasirSolver<'term, 'learn> {
     terminate_cb: OptTermCallbackStore<'term>,
     learner_cb: OptLearnCallbackStore<'learn>,
     stats: SolverStats,
+    dup_tracker: DuplicateClauseTracker,
 }
 
 impl Default for IpasirSolver<'_, '_> {
@@ -54,6 +56,7 @@ impl Default for IpasirSolver<'_, '_> {
             terminate_cb: Default::default(),
             learner_cb: Default::default(),
             stats: Default::default(),
+            dup_tracker: Default::default(),
         }
     }
 }
@@ -156,6 +159,9 @@ impl Solve for IpasirSolver<'_, '_> {
         }
         // Update wrapper-internal state
         self.stats.n_clauses += 1;
+        if self.dup_tracker.track(&clause) {
+            self.stats.n_duplicate_clauses += 1;
+        }
         clause.iter().for_each(|l| match self.stats.max_var {
             None => self.stats.max_var = Some(l.var()),
             Some(var) => {
@@ -175,6 +181,11 @@ impl Solve for IpasirSolver<'_, '_> {
         unsafe { ffi::ipasir_add(self.handle, 0) };
         Ok(())
     }
+
+    fn warn_duplicate_clauses(&mut self, on: bool) -> SolveMightFail {
+        self.dup_tracker.set_active(on);
+        Ok(())
+    }
 }
 
 impl SolveIncremental for IpasirSolver<'_, '_> {
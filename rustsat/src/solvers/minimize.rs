@@ -0,0 +1,106 @@
+//! # Core Minimization
+//!
+//! Minimization of unsatisfiable cores obtained from
+//! [`SolveIncremental::core`] to subset-minimal cores.
+
+use crate::types::{Lit, RsHashSet};
+
+use super::{SolveIncremental, SolverError, SolverResult};
+
+/// Algorithms for core minimization
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CoreMinimization {
+    /// Tries removing one literal at a time and keeps it only if the remaining
+    /// core becomes satisfiable. Requires one solver call per literal.
+    #[default]
+    Deletion,
+    /// QuickXplain-style recursive splitting of the core. Requires fewer solver
+    /// calls than [`CoreMinimization::Deletion`] if the minimal core is small
+    /// compared to the input core.
+    QuickXplain,
+}
+
+/// Minimizes an unsatisfiable core to a subset-minimal core. As returned by
+/// [`SolveIncremental::core`], the core is a clause of negated assumptions,
+/// i.e., the solver must be unsatisfiable under the negation of `core`. The
+/// minimal core is returned in the same format.
+///
+/// # Errors
+///
+/// - [`SolverError::Interrupted`] if a solver call is interrupted
+/// - Any error returned by the solver
+pub fn minimize_core<S: SolveIncremental>(
+    solver: &mut S,
+    core: Vec<Lit>,
+    method: CoreMinimization,
+) -> Result<Vec<Lit>, SolverError> {
+    match method {
+        CoreMinimization::Deletion => deletion(solver, core),
+        CoreMinimization::QuickXplain => {
+            // The formula itself might be unsatisfiable, in which case the
+            // empty core is minimal
+            let mut background = vec![];
+            quick_xplain(solver, &mut background, true, &core)
+        }
+    }
+}
+
+/// Checks whether the solver is unsatisfiable under the negation of the given
+/// core literals
+fn is_unsat<S: SolveIncremental>(solver: &mut S, core: &[Lit]) -> Result<bool, SolverError> {
+    let assumps: Vec<Lit> = core.iter().map(|&l| !l).collect();
+    match solver.solve_assumps(&assumps)? {
+        SolverResult::Sat => Ok(false),
+        SolverResult::Unsat => Ok(true),
+        SolverResult::Interrupted => Err(SolverError::Interrupted),
+    }
+}
+
+/// Deletion-based core minimization. The core found by the solver after a
+/// successful removal is used to remove further literals.
+fn deletion<S: SolveIncremental>(
+    solver: &mut S,
+    mut core: Vec<Lit>,
+) -> Result<Vec<Lit>, SolverError> {
+    let mut idx = 0;
+    while idx < core.len() {
+        let lit = core.remove(idx);
+        if is_unsat(solver, &core)? {
+            // Literals before `idx` are necessary and therefore contained in
+            // the new core
+            let refined: RsHashSet<Lit> = solver.core()?.into_iter().collect();
+            core.retain(|l| refined.contains(l));
+        } else {
+            core.insert(idx, lit);
+            idx += 1;
+        }
+    }
+    Ok(core)
+}
+
+/// QuickXplain core minimization. Returns a minimal subset of `candidates`
+/// that together with `background` is a core. `has_delta` indicates that
+/// literals were added to the background in the previous call.
+fn quick_xplain<S: SolveIncremental>(
+    solver: &mut S,
+    background: &mut Vec<Lit>,
+    has_delta: bool,
+    candidates: &[Lit],
+) -> Result<Vec<Lit>, SolverError> {
+    if has_delta && is_unsat(solver, background)? {
+        return Ok(vec![]);
+    }
+    if candidates.len() <= 1 {
+        return Ok(candidates.to_vec());
+    }
+    let (first, second) = candidates.split_at(candidates.len() / 2);
+    let n_background = background.len();
+    background.extend_from_slice(first);
+    let mut min_second = quick_xplain(solver, background, true, second)?;
+    background.truncate(n_background);
+    background.extend_from_slice(&min_second);
+    let mut min_first = quick_xplain(solver, background, !min_second.is_empty(), first)?;
+    background.truncate(n_background);
+    min_first.append(&mut min_second);
+    Ok(min_first)
+}
@@ -0,0 +1,123 @@
+//! # Portfolio of Different Solvers
+//!
+//! A [`Portfolio`] races several (possibly different) solvers on the same
+//! clauses in parallel and takes the first definite result.
+
+use super::{
+    race::{race, Race},
+    Interrupt, Solve, SolveMightFail, SolverError, SolverResult, SolverState,
+};
+use crate::{
+    instances::Cnf,
+    types::{Assignment, Clause, Lit, TernaryVal, Var},
+};
+
+/// Type-erased worker that creates a solver, adds the clauses and races it
+type Worker<'a> = Box<dyn Fn(&Cnf, Option<Var>, usize, &Race) -> SolveMightFail + Sync + 'a>;
+
+/// A portfolio of solvers that are raced on the same clauses. Since the
+/// solvers in this library are not [`Send`], they are added as factories and
+/// every call to [`Solve::solve`] creates fresh solvers in their own threads.
+/// The first definite result is returned and all other solvers are
+/// interrupted. All threads are joined before solving returns and a panicking
+/// solver only drops out of the race.
+///
+/// # Examples
+///
+/// ```
+/// # use rustsat::{lit, solvers::{Portfolio, Solve, SolverResult}};
+/// let mut portfolio = Portfolio::default();
+/// portfolio.add_solver(rustsat_minisat::core::Minisat::default);
+/// portfolio.add_solver(rustsat_minisat::simp::Minisat::default);
+/// portfolio.add_binary(lit![0], lit![1]).unwrap();
+/// portfolio.add_unit(!lit![0]).unwrap();
+/// assert_eq!(portfolio.solve().unwrap(), SolverResult::Sat);
+/// assert!(portfolio.lit_val(lit![1]).unwrap().to_bool_with_def(false));
+/// ```
+#[derive(Default)]
+pub struct Portfolio<'a> {
+    /// The workers for the solvers in the portfolio
+    workers: Vec<Worker<'a>>,
+    /// The clauses added to the portfolio
+    cnf: Cnf,
+    /// The highest variable in the clauses
+    max_var: Option<Var>,
+    /// The result of the last solver call and the model, if satisfiable
+    result: Option<(SolverResult, Option<Assignment>)>,
+}
+
+impl<'a> Portfolio<'a> {
+    /// Adds a solver to the portfolio. `solver_factory` is called in the
+    /// worker thread every time the portfolio is solved.
+    pub fn add_solver<S, F>(&mut self, solver_factory: F)
+    where
+        S: Solve + Interrupt,
+        F: Fn() -> S + Sync + 'a,
+    {
+        self.workers.push(Box::new(move |cnf, max_var, idx, race| {
+            let mut solver = solver_factory();
+            solver.add_cnf(cnf.clone())?;
+            race.run(idx, solver, |solver| match max_var {
+                Some(max_var) => solver.solution(max_var),
+                None => Ok(Assignment::default()),
+            })
+        }));
+    }
+
+    /// Gets the number of solvers in the portfolio
+    pub fn n_solvers(&self) -> usize {
+        self.workers.len()
+    }
+}
+
+impl Extend<Clause> for Portfolio<'_> {
+    fn extend<T: IntoIterator<Item = Clause>>(&mut self, iter: T) {
+        iter.into_iter()
+            .for_each(|cl| self.add_clause(cl).expect("Error adding clause in extend"))
+    }
+}
+
+impl Solve for Portfolio<'_> {
+    fn signature(&self) -> &'static str {
+        "portfolio"
+    }
+
+    /// Races all solvers in the portfolio. If all solvers were interrupted,
+    /// [`SolverResult::Interrupted`] is returned. If no solver finished
+    /// because of errors or panics, the first error is returned.
+    fn solve(&mut self) -> Result<SolverResult, SolverError> {
+        if self.workers.is_empty() {
+            return Err(SolverError::Api(
+                "the portfolio does not contain any solvers".to_string(),
+            ));
+        }
+        self.result = None;
+        let winner = race(self.workers.len(), |idx, race| {
+            self.workers[idx](&self.cnf, self.max_var, idx, race)
+        })?;
+        let res = winner.0;
+        if res != SolverResult::Interrupted {
+            self.result = Some(winner);
+        }
+        Ok(res)
+    }
+
+    fn lit_val(&self, lit: Lit) -> Result<TernaryVal, SolverError> {
+        match &self.result {
+            Some((SolverResult::Sat, Some(model))) => Ok(model.lit_value(lit)),
+            Some((SolverResult::Unsat, _)) => {
+                Err(SolverError::State(SolverState::Unsat, SolverState::Sat))
+            }
+            _ => Err(SolverError::State(SolverState::Input, SolverState::Sat)),
+        }
+    }
+
+    fn add_clause(&mut self, clause: Clause) -> SolveMightFail {
+        if let Some(cl_max) = clause.iter().map(|l| l.var()).max() {
+            self.max_var = Some(self.max_var.map_or(cl_max, |mv| std::cmp::max(mv, cl_max)));
+        }
+        self.result = None;
+        self.cnf.add_clause(clause);
+        Ok(())
+    }
+}
@@ -0,0 +1,134 @@
+//! # Racing Solvers in Parallel
+//!
+//! Shared machinery of the portfolios that race several solvers in their own
+//! threads and take the first definite result. A racer that fails or panics
+//! only drops out of the race: the shared state ignores lock poisoning and
+//! the panic is reported as an error if no other racer finishes.
+
+use std::{
+    sync::{Mutex, MutexGuard, PoisonError},
+    thread,
+};
+
+use super::{Interrupt, InterruptSolver, Solve, SolveMightFail, SolverError, SolverResult};
+use crate::types::Assignment;
+
+/// Type-erased interrupter of a solver in the race
+type BoxedInterrupter = Box<dyn InterruptSolver + Send>;
+
+/// The result of a race and the model, if satisfiable
+pub(super) type RaceResult = (SolverResult, Option<Assignment>);
+
+/// Shared state of the threads in a race
+struct RaceState {
+    /// The interrupters of the solvers that are currently solving
+    interrupters: Vec<Option<BoxedInterrupter>>,
+    /// The first definite result
+    winner: Option<RaceResult>,
+}
+
+/// A race between solvers running in parallel threads
+pub(super) struct Race {
+    state: Mutex<RaceState>,
+}
+
+/// Deregisters the interrupter of a solver when dropped, also if the solver
+/// panics, so that no interrupter outlives its solver
+struct Registration<'race> {
+    race: &'race Race,
+    idx: usize,
+}
+
+impl Drop for Registration<'_> {
+    fn drop(&mut self) {
+        self.race.lock().interrupters[self.idx] = None;
+    }
+}
+
+/// Runs `n_racers` racers in their own threads. `racer` is called with the
+/// index of the racer and should create a solver and pass it to [`Race::run`].
+/// All threads are joined before returning. If all racers were interrupted,
+/// [`SolverResult::Interrupted`] is returned. If no racer finished because of
+/// errors or panics, the first error is returned.
+pub(super) fn race<F>(n_racers: usize, racer: F) -> Result<RaceResult, SolverError>
+where
+    F: Fn(usize, &Race) -> SolveMightFail + Sync,
+{
+    let race = Race {
+        state: Mutex::new(RaceState {
+            interrupters: (0..n_racers).map(|_| None).collect(),
+            winner: None,
+        }),
+    };
+    let results: Vec<SolveMightFail> = thread::scope(|scope| {
+        let handles: Vec<_> = (0..n_racers)
+            .map(|idx| {
+                let race = &race;
+                let racer = &racer;
+                scope.spawn(move || racer(idx, race))
+            })
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| {
+                handle.join().unwrap_or_else(|_| {
+                    Err(SolverError::Api("a racing solver panicked".to_string()))
+                })
+            })
+            .collect()
+    });
+    let state = race
+        .state
+        .into_inner()
+        .unwrap_or_else(PoisonError::into_inner);
+    if let Some(winner) = state.winner {
+        return Ok(winner);
+    }
+    results.into_iter().collect::<SolveMightFail>()?;
+    Ok((SolverResult::Interrupted, None))
+}
+
+impl Race {
+    /// Locks the state, ignoring poisoning by a panicked racer
+    fn lock(&self) -> MutexGuard<'_, RaceState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Solves `solver` as the racer with index `idx`. If the solver finishes
+    /// first with a definite result, it wins the race and all other racers
+    /// are interrupted. The model of a satisfiable winner is obtained with
+    /// `model`.
+    pub(super) fn run<S, M>(&self, idx: usize, mut solver: S, model: M) -> SolveMightFail
+    where
+        S: Solve + Interrupt,
+        M: FnOnce(&S) -> Result<Assignment, SolverError>,
+    {
+        {
+            let mut state = self.lock();
+            if state.winner.is_some() {
+                return Ok(());
+            }
+            state.interrupters[idx] = Some(Box::new(solver.interrupter()));
+        }
+        let registration = Registration { race: self, idx };
+        let res = solver.solve();
+        drop(registration);
+        let mut state = self.lock();
+        let res = res?;
+        if res == SolverResult::Interrupted || state.winner.is_some() {
+            return Ok(());
+        }
+        let model = if res == SolverResult::Sat {
+            Some(model(&solver)?)
+        } else {
+            None
+        };
+        state.winner = Some((res, model));
+        state
+            .interrupters
+            .iter()
+            .flatten()
+            .for_each(|interrupter| interrupter.interrupt());
+        Ok(())
+    }
+}
@@ -0,0 +1,214 @@
+//! # Clause Recorder
+//!
+//! A wrapper around a solver that records all clauses added to it. Clauses can
+//! optionally be tagged with an origin, e.g., the index of the constraint that
+//! produced them, which allows for mapping clauses back to their source.
+//! If the wrapped solver logs a proof, the origins are also written to the
+//! proof as comments.
+
+use std::path::Path;
+
+use crate::types::{Assignment, Clause, Lit, TernaryVal, Var};
+
+use super::{
+    ProofLogging, PropagateQuery, Solve, SolveIncremental, SolveMightFail, SolverError,
+    SolverResult,
+};
+
+/// A clause recorded by a [`ClauseRecorder`] together with its origin tag
+pub type RecordedClause = (Clause, Option<u32>);
+
+/// Function writing a comment to the proof of a solver
+type ProofCommentFn<S> = fn(&mut S, &str) -> Result<(), SolverError>;
+
+/// Wrapper around a solver recording all added clauses and their origins
+#[derive(Debug, Default)]
+pub struct ClauseRecorder<S> {
+    /// The wrapped solver
+    solver: S,
+    /// The clauses added so far
+    clauses: Vec<RecordedClause>,
+    /// Writes a comment to the proof of the wrapped solver, set while proof
+    /// logging is enabled
+    proof_comment: Option<ProofCommentFn<S>>,
+}
+
+impl<S> ClauseRecorder<S> {
+    /// Creates a new clause recorder around a solver
+    pub fn new(solver: S) -> Self {
+        ClauseRecorder {
+            solver,
+            clauses: vec![],
+            proof_comment: None,
+        }
+    }
+
+    /// Gets the recorded clauses in the order they were added. Clauses added
+    /// without an origin have the origin [`None`].
+    pub fn recorded(&self) -> &[RecordedClause] {
+        &self.clauses
+    }
+
+    /// Gets the recorded clauses that were added with the given origin
+    pub fn recorded_with_origin(&self, origin: u32) -> impl Iterator<Item = &Clause> {
+        self.clauses
+            .iter()
+            .filter_map(move |(cl, o)| if *o == Some(origin) { Some(cl) } else { None })
+    }
+
+    /// Gets a reference to the wrapped solver
+    pub fn solver(&self) -> &S {
+        &self.solver
+    }
+
+    /// Gets a mutable reference to the wrapped solver. Note that clauses added
+    /// directly to the solver are not recorded.
+    pub fn solver_mut(&mut self) -> &mut S {
+        &mut self.solver
+    }
+
+    /// Consumes the recorder and returns the wrapped solver and the recorded
+    /// clauses
+    pub fn into_inner(self) -> (S, Vec<RecordedClause>) {
+        (self.solver, self.clauses)
+    }
+}
+
+impl<S: Solve> ClauseRecorder<S> {
+    /// Adds a clause to the solver and records it with an origin tag. While
+    /// proof logging is enabled, the clause is preceded by a comment
+    /// `c origin <origin>: <clause in DIMACS>` in the proof.
+    pub fn add_clause_with_origin(&mut self, clause: Clause, origin: u32) -> SolveMightFail {
+        if let Some(proof_comment) = self.proof_comment {
+            let mut comment = format!("origin {}:", origin);
+            for lit in &clause {
+                comment.push_str(&format!(" {}", lit.to_ipasir()));
+            }
+            comment.push_str(" 0");
+            proof_comment(&mut self.solver, &comment)?;
+        }
+        self.solver.add_clause(clause.clone())?;
+        self.clauses.push((clause, Some(origin)));
+        Ok(())
+    }
+}
+
+impl<S: Solve> Extend<Clause> for ClauseRecorder<S> {
+    fn extend<T: IntoIterator<Item = Clause>>(&mut self, iter: T) {
+        iter.into_iter()
+            .for_each(|cl| self.add_clause(cl).expect("Error adding clause in extend"))
+    }
+}
+
+impl<S: Solve> Solve for ClauseRecorder<S> {
+    fn signature(&self) -> &'static str {
+        self.solver.signature()
+    }
+
+    fn reserve(&mut self, max_var: Var) -> SolveMightFail {
+        self.solver.reserve(max_var)
+    }
+
+    fn solve(&mut self) -> Result<SolverResult, SolverError> {
+        self.solver.solve()
+    }
+
+    fn solution(&self, high_var: Var) -> Result<Assignment, SolverError> {
+        self.solver.solution(high_var)
+    }
+
+    fn lit_val(&self, lit: Lit) -> Result<TernaryVal, SolverError> {
+        self.solver.lit_val(lit)
+    }
+
+    fn add_clause(&mut self, clause: Clause) -> SolveMightFail {
+        self.solver.add_clause(clause.clone())?;
+        self.clauses.push((clause, None));
+        Ok(())
+    }
+
+    fn warn_duplicate_clauses(&mut self, on: bool) -> SolveMightFail {
+        self.solver.warn_duplicate_clauses(on)
+    }
+}
+
+impl<S: SolveIncremental> SolveIncremental for ClauseRecorder<S> {
+    fn solve_assumps(&mut self, assumps: &[Lit]) -> Result<SolverResult, SolverError> {
+        self.solver.solve_assumps(assumps)
+    }
+
+    fn core(&mut self) -> Result<Vec<Lit>, SolverError> {
+        self.solver.core()
+    }
+}
+
+impl<S: ProofLogging> ProofLogging for ClauseRecorder<S> {
+    fn enable_proof<P: AsRef<Path>>(&mut self, path: P) -> Result<(), SolverError> {
+        self.solver.enable_proof(path)?;
+        self.proof_comment = Some(S::proof_comment);
+        Ok(())
+    }
+
+    fn close_proof(&mut self) -> Result<(), SolverError> {
+        self.proof_comment = None;
+        self.solver.close_proof()
+    }
+
+    fn proof_comment(&mut self, comment: &str) -> Result<(), SolverError> {
+        self.solver.proof_comment(comment)
+    }
+}
+
+/// Propagation is done on the recorded clauses, independent of the wrapped
+/// solver. Clauses added directly to the wrapped solver are not considered.
+impl<S: Solve> PropagateQuery for ClauseRecorder<S> {
+    fn propagate(&mut self, assumps: Vec<Lit>) -> Result<(Vec<Lit>, bool), SolverError> {
+        let mut assignment = Assignment::default();
+        let mut assigned = Vec::with_capacity(assumps.len());
+        for a in assumps {
+            match assignment.lit_value(a) {
+                TernaryVal::True => (),
+                TernaryVal::False => return Ok((assigned, true)),
+                TernaryVal::DontCare => {
+                    assignment.assign_lit(a);
+                    assigned.push(a);
+                }
+            }
+        }
+        loop {
+            let n_assigned = assigned.len();
+            for (cl, _) in &self.clauses {
+                let mut unassigned = None;
+                let mut n_unassigned = 0;
+                let mut satisfied = false;
+                for &l in cl {
+                    match assignment.lit_value(l) {
+                        TernaryVal::True => {
+                            satisfied = true;
+                            break;
+                        }
+                        TernaryVal::False => (),
+                        TernaryVal::DontCare => {
+                            n_unassigned += 1;
+                            unassigned = Some(l);
+                        }
+                    }
+                }
+                if satisfied {
+                    continue;
+                }
+                match (n_unassigned, unassigned) {
+                    (0, _) => return Ok((assigned, true)),
+                    (1, Some(unit)) => {
+                        assignment.assign_lit(unit);
+                        assigned.push(unit);
+                    }
+                    _ => (),
+                }
+            }
+            if assigned.len() == n_assigned {
+                return Ok((assigned, false));
+            }
+        }
+    }
+}
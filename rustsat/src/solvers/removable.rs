@@ -0,0 +1,100 @@
+//! # Removable Clauses
+//!
+//! Emulation of clause deletion for incremental solvers. Every clause added
+//! through [`RemovableClauses`] belongs to a group and is extended by the
+//! negated activation literal of that group. The group is only enforced when
+//! its activation literal is assumed and can be removed for good by adding
+//! the negated activation literal as a unit.
+
+use crate::{
+    instances::ManageVars,
+    types::{Clause, Lit, RsHashMap},
+};
+
+use super::{Solve, SolveMightFail, SolverError};
+
+/// Tracks the activation literals of groups of removable clauses. In contrast
+/// to [`super::ClauseStack`], this does not wrap the solver and groups can be
+/// enabled and removed in any order.
+///
+/// # Examples
+///
+/// ```
+/// # use rustsat::{clause, lit, instances::{BasicVarManager, ManageVars}, var};
+/// # use rustsat::solvers::{RemovableClauses, Solve, SolveIncremental, SolverResult};
+/// let mut solver = rustsat_minisat::core::Minisat::default();
+/// let mut var_manager = BasicVarManager::default();
+/// var_manager.increase_next_free(var![1]);
+/// let mut removable = RemovableClauses::default();
+/// removable.add_clause(0, clause![lit![0]], &mut solver, &mut var_manager).unwrap();
+/// removable.add_clause(1, clause![!lit![0]], &mut solver, &mut var_manager).unwrap();
+/// assert_eq!(solver.solve_assumps(&removable.assumps()).unwrap(), SolverResult::Unsat);
+/// removable.deactivate(1, &mut solver).unwrap();
+/// assert_eq!(solver.solve_assumps(&removable.assumps()).unwrap(), SolverResult::Sat);
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct RemovableClauses {
+    /// The activation literals of the groups that have not been removed
+    act_lits: RsHashMap<usize, Lit>,
+}
+
+impl RemovableClauses {
+    /// Adds a clause to a group of removable clauses. If the group does not
+    /// exist yet, a new activation literal is taken from the variable
+    /// manager.
+    pub fn add_clause<S>(
+        &mut self,
+        group: usize,
+        mut clause: Clause,
+        solver: &mut S,
+        var_manager: &mut dyn ManageVars,
+    ) -> SolveMightFail
+    where
+        S: Solve + ?Sized,
+    {
+        let act = *self
+            .act_lits
+            .entry(group)
+            .or_insert_with(|| var_manager.new_lit());
+        clause.add(!act);
+        solver.add_clause(clause)
+    }
+
+    /// Gets the assumption that enforces the clauses in a group. Returns
+    /// `None` if the group does not exist.
+    pub fn activate(&self, group: usize) -> Option<Lit> {
+        self.act_lits.get(&group).copied()
+    }
+
+    /// Permanently removes a group by adding the negated activation literal
+    /// as a unit clause. Returns the added unit or `None` if the group did
+    /// not exist. After this, the group identifier can be reused for a new
+    /// group with a fresh activation literal.
+    pub fn deactivate<S>(
+        &mut self,
+        group: usize,
+        solver: &mut S,
+    ) -> Result<Option<Lit>, SolverError>
+    where
+        S: Solve + ?Sized,
+    {
+        match self.act_lits.remove(&group) {
+            Some(act) => {
+                solver.add_unit(!act)?;
+                Ok(Some(!act))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Gets the assumptions that enforce all groups that have not been
+    /// removed
+    pub fn assumps(&self) -> Vec<Lit> {
+        self.act_lits.values().copied().collect()
+    }
+
+    /// Gets the number of groups that have not been removed
+    pub fn n_groups(&self) -> usize {
+        self.act_lits.len()
+    }
+}
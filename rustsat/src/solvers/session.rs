@@ -0,0 +1,68 @@
+//! # Query Session
+//!
+//! A wrapper around an incremental solver for answering a sequence of related
+//! queries that only differ in their assumptions. All queries are answered by
+//! the same solver, so clauses learned in earlier queries are reused in later
+//! ones.
+
+use crate::types::{Assignment, Lit};
+
+use super::{SolveIncremental, SolveStats, SolverError, SolverResult};
+
+/// Result of a query in a [`QuerySession`]. Contains the model if the query
+/// was satisfiable and the core if the query was unsatisfiable.
+pub type QueryResult = (SolverResult, Option<Assignment>, Option<Vec<Lit>>);
+
+/// Wrapper around an incremental solver answering queries under assumptions
+#[derive(Debug, Default)]
+pub struct QuerySession<S> {
+    /// The wrapped solver
+    solver: S,
+    /// The number of queries answered so far
+    n_queries: usize,
+}
+
+impl<S> QuerySession<S> {
+    /// Creates a new query session around a solver
+    pub fn new(solver: S) -> Self {
+        QuerySession {
+            solver,
+            n_queries: 0,
+        }
+    }
+
+    /// Gets the number of queries answered so far
+    pub fn n_queries(&self) -> usize {
+        self.n_queries
+    }
+
+    /// Gets a reference to the wrapped solver
+    pub fn solver(&self) -> &S {
+        &self.solver
+    }
+
+    /// Gets a mutable reference to the wrapped solver, e.g., for adding
+    /// clauses shared by all following queries
+    pub fn solver_mut(&mut self) -> &mut S {
+        &mut self.solver
+    }
+
+    /// Consumes the session and returns the wrapped solver
+    pub fn into_inner(self) -> S {
+        self.solver
+    }
+}
+
+impl<S: SolveIncremental + SolveStats> QuerySession<S> {
+    /// Answers a query under the given assumptions. Returns the model for
+    /// satisfiable queries and the core for unsatisfiable ones.
+    pub fn query(&mut self, assumps: &[Lit]) -> Result<QueryResult, SolverError> {
+        self.n_queries += 1;
+        let res = self.solver.solve_assumps(assumps)?;
+        match res {
+            SolverResult::Sat => Ok((res, Some(self.solver.full_solution()?), None)),
+            SolverResult::Unsat => Ok((res, None, Some(self.solver.core()?))),
+            SolverResult::Interrupted => Ok((res, None, None)),
+        }
+    }
+}
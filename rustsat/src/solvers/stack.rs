@@ -0,0 +1,133 @@
+//! # Clause Stack
+//!
+//! A wrapper around an incremental solver that allows for retracting clauses
+//! in last-in-first-out order. Since incremental solvers can not delete
+//! clauses, this is implemented via activation literals that are assumed
+//! while the clause is on the stack.
+
+use crate::{
+    instances::ManageVars,
+    types::{Assignment, Clause, Lit, TernaryVal, Var},
+};
+
+use super::{Solve, SolveIncremental, SolveMightFail, SolverError, SolverResult};
+
+/// Wrapper around an incremental solver maintaining a stack of retractable
+/// clauses
+#[derive(Debug, Default)]
+pub struct ClauseStack<S> {
+    /// The wrapped solver
+    solver: S,
+    /// The activation literals of the clauses on the stack
+    act_lits: Vec<Lit>,
+}
+
+impl<S> ClauseStack<S> {
+    /// Creates a new clause stack around a solver
+    pub fn new(solver: S) -> Self {
+        ClauseStack {
+            solver,
+            act_lits: vec![],
+        }
+    }
+
+    /// Gets the number of clauses on the stack
+    pub fn depth(&self) -> usize {
+        self.act_lits.len()
+    }
+
+    /// Gets a reference to the wrapped solver
+    pub fn solver(&self) -> &S {
+        &self.solver
+    }
+
+    /// Gets a mutable reference to the wrapped solver. Note that queries
+    /// directly to the solver do not respect the clause stack.
+    pub fn solver_mut(&mut self) -> &mut S {
+        &mut self.solver
+    }
+
+    /// Consumes the stack and returns the wrapped solver
+    pub fn into_inner(self) -> S {
+        self.solver
+    }
+}
+
+impl<S: SolveIncremental> ClauseStack<S> {
+    /// Pushes a clause on top of the stack. The activation literal for the
+    /// clause is taken from the variable manager.
+    pub fn push_clause(
+        &mut self,
+        mut clause: Clause,
+        var_manager: &mut dyn ManageVars,
+    ) -> SolveMightFail {
+        let act = var_manager.new_lit();
+        clause.add(!act);
+        self.solver.add_clause(clause)?;
+        self.act_lits.push(act);
+        Ok(())
+    }
+
+    /// Deactivates the clause on top of the stack. Returns `false` if the
+    /// stack was empty.
+    pub fn pop_clause(&mut self) -> Result<bool, SolverError> {
+        match self.act_lits.pop() {
+            Some(act) => {
+                self.solver.add_unit(!act)?;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+}
+
+impl<S: SolveIncremental> Extend<Clause> for ClauseStack<S> {
+    fn extend<T: IntoIterator<Item = Clause>>(&mut self, iter: T) {
+        self.solver.extend(iter)
+    }
+}
+
+impl<S: SolveIncremental> Solve for ClauseStack<S> {
+    fn signature(&self) -> &'static str {
+        self.solver.signature()
+    }
+
+    fn reserve(&mut self, max_var: Var) -> SolveMightFail {
+        self.solver.reserve(max_var)
+    }
+
+    fn solve(&mut self) -> Result<SolverResult, SolverError> {
+        self.solve_assumps(&[])
+    }
+
+    fn solution(&self, high_var: Var) -> Result<Assignment, SolverError> {
+        self.solver.solution(high_var)
+    }
+
+    fn lit_val(&self, lit: Lit) -> Result<TernaryVal, SolverError> {
+        self.solver.lit_val(lit)
+    }
+
+    fn add_clause(&mut self, clause: Clause) -> SolveMightFail {
+        self.solver.add_clause(clause)
+    }
+
+    fn warn_duplicate_clauses(&mut self, on: bool) -> SolveMightFail {
+        self.solver.warn_duplicate_clauses(on)
+    }
+}
+
+impl<S: SolveIncremental> SolveIncremental for ClauseStack<S> {
+    fn solve_assumps(&mut self, assumps: &[Lit]) -> Result<SolverResult, SolverError> {
+        let mut all_assumps = Vec::with_capacity(self.act_lits.len() + assumps.len());
+        all_assumps.extend_from_slice(&self.act_lits);
+        all_assumps.extend_from_slice(assumps);
+        self.solver.solve_assumps(&all_assumps)
+    }
+
+    fn core(&mut self) -> Result<Vec<Lit>, SolverError> {
+        let mut core = self.solver.core()?;
+        core.retain(|&l| !self.act_lits.contains(&!l));
+        Ok(core)
+    }
+}
@@ -0,0 +1,106 @@
+//! # Result Verification
+//!
+//! Independent checking of the results of solver queries. Models of
+//! satisfiable queries are checked against the instance, cores of
+//! unsatisfiable queries are checked by solving the instance under the core
+//! with a fresh solver.
+
+use thiserror::Error;
+
+use crate::{
+    instances::{BasicVarManager, Cnf, ManageVars, SatInstance},
+    types::{Lit, TernaryVal},
+};
+
+use super::{QueryResult, SolveIncremental, SolverError, SolverResult};
+
+/// Errors detected while verifying the result of a solver query
+#[derive(Error, Debug)]
+pub enum VerificationError {
+    /// The result is satisfiable but no model was given
+    #[error("satisfiable result without model")]
+    MissingModel,
+    /// The result is unsatisfiable but no core was given
+    #[error("unsatisfiable result without core")]
+    MissingCore,
+    /// The model does not satisfy the instance
+    #[error("model does not satisfy the instance")]
+    ModelNotSat,
+    /// The model does not satisfy an assumption
+    #[error("model violates assumption {0}")]
+    ModelViolatesAssumption(Lit),
+    /// The core contains a literal that is not a negated assumption
+    #[error("core literal {0} is not a negated assumption")]
+    CoreNotFromAssumptions(Lit),
+    /// The instance is satisfiable under the core
+    #[error("instance is satisfiable under the core")]
+    CoreSat,
+    /// The fresh solver returned an error
+    #[error("solver error: {0}")]
+    Solver(SolverError),
+}
+
+impl From<SolverError> for VerificationError {
+    fn from(se: SolverError) -> Self {
+        VerificationError::Solver(se)
+    }
+}
+
+/// Verifies the result of a query to a solver, e.g., returned by
+/// [`QuerySession::query`](super::QuerySession::query), for an instance and the
+/// assumptions of the query.
+///
+/// For a satisfiable result, the model is checked against the instance and the
+/// assumptions. For an unsatisfiable result, the core is checked to only
+/// contain negated assumptions and the instance is solved under the core with
+/// a solver created by `solver_factory`. Interrupted results are not checked.
+///
+/// # Errors
+///
+/// A [`VerificationError`] describing why the result is invalid.
+pub fn verify_result<VM, S, F>(
+    instance: &SatInstance<VM>,
+    assumps: &[Lit],
+    result: &QueryResult,
+    solver_factory: F,
+) -> Result<(), VerificationError>
+where
+    VM: ManageVars,
+    S: SolveIncremental,
+    F: FnOnce() -> S,
+{
+    let (res, model, core) = result;
+    match res {
+        SolverResult::Sat => {
+            let model = model.as_ref().ok_or(VerificationError::MissingModel)?;
+            if !instance.is_sat(model) {
+                return Err(VerificationError::ModelNotSat);
+            }
+            if let Some(&lit) = assumps
+                .iter()
+                .find(|&&l| model.lit_value(l) != TernaryVal::True)
+            {
+                return Err(VerificationError::ModelViolatesAssumption(lit));
+            }
+            Ok(())
+        }
+        SolverResult::Unsat => {
+            let core = core.as_ref().ok_or(VerificationError::MissingCore)?;
+            if let Some(&lit) = core.iter().find(|&&l| !assumps.contains(&!l)) {
+                return Err(VerificationError::CoreNotFromAssumptions(lit));
+            }
+            let Ok(cnf) = Cnf::try_from_instance(instance, &mut BasicVarManager::default()) else {
+                // The instance is trivially unsatisfiable
+                return Ok(());
+            };
+            let mut solver = solver_factory();
+            solver.add_cnf(cnf)?;
+            let core_assumps: Vec<Lit> = core.iter().map(|&l| !l).collect();
+            match solver.solve_assumps(&core_assumps)? {
+                SolverResult::Sat => Err(VerificationError::CoreSat),
+                _ => Ok(()),
+            }
+        }
+        SolverResult::Interrupted => Ok(()),
+    }
+}
@@ -0,0 +1,77 @@
+//! # Shared Test Utilities
+//!
+//! Brute-force checks of small encodings against all assignments of their
+//! variables.
+
+use crate::{
+    instances::Cnf,
+    types::{Assignment, Lit, TernaryVal, Var},
+};
+
+/// Gets the assignment of the variables `0..n_vars` where variable `idx` is
+/// true if bit `idx` of `bits` is set
+pub(crate) fn assignment_from_bits(bits: u32, n_vars: u32) -> Assignment {
+    Assignment::from(
+        (0..n_vars)
+            .map(|idx| TernaryVal::from(bits & (1 << idx) != 0))
+            .collect::<Vec<_>>(),
+    )
+}
+
+/// Iterates over all assignments of the variables `0..n_vars`
+pub(crate) fn all_assignments(n_vars: u32) -> impl Iterator<Item = Assignment> {
+    (0u32..1 << n_vars).map(move |bits| assignment_from_bits(bits, n_vars))
+}
+
+/// Checks whether the assignment of the input variables `0..n_inputs` given by
+/// the bits of `inputs` can be extended to the variables `0..n_vars` such that
+/// `cnf` is satisfied and all `assumps` are true. Values forced by unit
+/// propagation are fixed first, the remaining variables are enumerated.
+pub(crate) fn extendable(
+    cnf: &Cnf,
+    assumps: &[Lit],
+    inputs: u32,
+    n_inputs: u32,
+    n_vars: u32,
+) -> bool {
+    let mut assign = assignment_from_bits(inputs, n_inputs);
+    for &a in assumps {
+        match assign.lit_value(a) {
+            TernaryVal::True => (),
+            TernaryVal::False => return false,
+            TernaryVal::DontCare => assign.assign_lit(a),
+        }
+    }
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for cl in cnf.iter() {
+            if cl.iter().any(|&l| assign.lit_value(l) == TernaryVal::True) {
+                continue;
+            }
+            let mut open = cl
+                .iter()
+                .filter(|&&l| assign.lit_value(l) == TernaryVal::DontCare);
+            match (open.next(), open.next()) {
+                // All literals are false
+                (None, _) => return false,
+                (Some(&l), None) => {
+                    assign.assign_lit(l);
+                    changed = true;
+                }
+                _ => (),
+            }
+        }
+    }
+    let open: Vec<Var> = (n_inputs..n_vars)
+        .map(Var::new)
+        .filter(|&v| assign.var_value(v) == TernaryVal::DontCare)
+        .collect();
+    (0u32..1 << open.len()).any(|bits| {
+        let mut assign = assign.clone();
+        for (idx, &v) in open.iter().enumerate() {
+            assign.assign_var(v, TernaryVal::from(bits & (1 << idx) != 0));
+        }
+        cnf.is_satisfied(&assign)
+    })
+}
@@ -6,15 +6,18 @@ use core::ffi::c_int;
 use std::{
     fmt,
     ops::{self, Index, IndexMut},
+    str::FromStr,
 };
 
 use thiserror::Error;
 
+use crate::solvers::{Solve, SolverError};
+
 #[cfg(feature = "pyapi")]
 use pyo3::{exceptions::PyValueError, prelude::*};
 
 pub mod constraints;
-pub use constraints::Clause;
+pub use constraints::{Clause, ClauseStatus};
 
 /// The hash map to use throughout the library
 #[cfg(feature = "fxhash")]
@@ -189,6 +192,36 @@ impl fmt::Display for Var {
     }
 }
 
+/// Variables can be parsed from their [`Display`](std::fmt::Display) form,
+/// e.g., `x3`
+impl FromStr for Var {
+    type Err = TypeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let idx = s
+            .strip_prefix('x')
+            .and_then(|idx| idx.parse::<u32>().ok())
+            .ok_or_else(|| TypeError::Parse(s.to_string()))?;
+        Var::new_with_error(idx)
+    }
+}
+
+/// Variables are serialized as their index
+#[cfg(feature = "serde")]
+impl serde::Serialize for Var {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u32(self.idx)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Var {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let idx = <u32 as serde::Deserialize>::deserialize(deserializer)?;
+        Var::new_with_error(idx).map_err(serde::de::Error::custom)
+    }
+}
+
 /// More easily creates variables. Mainly used in tests.
 ///
 /// # Examples
@@ -440,6 +473,49 @@ impl fmt::Display for Lit {
     }
 }
 
+/// Literals can be parsed from their [`Display`](std::fmt::Display) form,
+/// e.g., `~x3`, or from their signed DIMACS/IPASIR form, e.g., `-4`
+impl FromStr for Lit {
+    type Err = TypeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(var) = s.strip_prefix('~') {
+            return Ok(!Var::from_str(var)
+                .map_err(|err| match err {
+                    TypeError::Parse(_) => TypeError::Parse(s.to_string()),
+                    err => err,
+                })?
+                .pos_lit());
+        }
+        if s.starts_with('x') {
+            return Ok(Var::from_str(s)?.pos_lit());
+        }
+        match s.parse::<c_int>() {
+            Ok(val) => Lit::from_ipasir(val),
+            Err(_) => Err(TypeError::Parse(s.to_string())),
+        }
+    }
+}
+
+/// Literals are serialized as their IPASIR integer, matching DIMACS
+#[cfg(feature = "serde")]
+impl serde::Serialize for Lit {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let ipasir = self
+            .to_ipasir_with_error()
+            .map_err(serde::ser::Error::custom)?;
+        serializer.serialize_i32(ipasir)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Lit {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let ipasir = <c_int as serde::Deserialize>::deserialize(deserializer)?;
+        Lit::from_ipasir(ipasir).map_err(serde::de::Error::custom)
+    }
+}
+
 #[cfg(feature = "pyapi")]
 #[pymethods]
 impl Lit {
@@ -521,6 +597,7 @@ macro_rules! ipasir_lit {
 
 /// Ternary value assigned to a literal or variable, including possible "don't care"
 #[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(u8)]
 pub enum TernaryVal {
     /// Positive assignment.
@@ -540,6 +617,28 @@ impl TernaryVal {
             TernaryVal::DontCare => def,
         }
     }
+
+    /// Conjunction in Kleene's three-valued logic, where "don't care" is
+    /// treated as unknown. The result is false if either value is false.
+    #[must_use]
+    pub fn and(self, other: TernaryVal) -> TernaryVal {
+        match (self, other) {
+            (TernaryVal::False, _) | (_, TernaryVal::False) => TernaryVal::False,
+            (TernaryVal::True, TernaryVal::True) => TernaryVal::True,
+            _ => TernaryVal::DontCare,
+        }
+    }
+
+    /// Disjunction in Kleene's three-valued logic, where "don't care" is
+    /// treated as unknown. The result is true if either value is true.
+    #[must_use]
+    pub fn or(self, other: TernaryVal) -> TernaryVal {
+        match (self, other) {
+            (TernaryVal::True, _) | (_, TernaryVal::True) => TernaryVal::True,
+            (TernaryVal::False, TernaryVal::False) => TernaryVal::False,
+            _ => TernaryVal::DontCare,
+        }
+    }
 }
 
 /// Ternary values can be printed with the [`Display`](std::fmt::Display) trait
@@ -605,6 +704,25 @@ pub struct Assignment {
 }
 
 impl Assignment {
+    /// Reads the values of `vars` from a solver in the satisfied state.
+    /// Variables not in `vars` are [`TernaryVal::DontCare`].
+    ///
+    /// # Errors
+    ///
+    /// If the solver cannot return the value of one of the variables, e.g.,
+    /// because it is not in the satisfied state
+    pub fn from_solver<S, I>(solver: &S, vars: I) -> Result<Self, SolverError>
+    where
+        S: Solve + ?Sized,
+        I: IntoIterator<Item = Var>,
+    {
+        let mut assign = Assignment::default();
+        for var in vars {
+            assign.assign_var(var, solver.var_val(var)?);
+        }
+        Ok(assign)
+    }
+
     /// Get the value that the solution assigns to a variable.
     /// If the variable is not included in the solution, will return `TernaryVal::DontCare`.
     pub fn var_value(&self, var: Var) -> TernaryVal {
@@ -672,6 +790,41 @@ impl Assignment {
             Some(var![self.assignment.len() as u32 - 1])
         }
     }
+
+    /// Projects the assignment onto a set of variables. All other variables
+    /// are [`TernaryVal::DontCare`] in the projection.
+    pub fn project(&self, vars: &[Var]) -> Assignment {
+        let mut proj = Assignment::default();
+        vars.iter()
+            .for_each(|&var| proj.assign_var(var, self.var_value(var)));
+        proj
+    }
+
+    /// Gets the variables that have a different value in `other`, in
+    /// increasing order. A variable that is [`TernaryVal::DontCare`] in only
+    /// one of the assignments also differs.
+    pub fn diff(&self, other: &Assignment) -> Vec<Var> {
+        let len = std::cmp::max(self.assignment.len(), other.assignment.len());
+        (0..len)
+            .map(|idx| var![idx as u32])
+            .filter(|&var| self.var_value(var) != other.var_value(var))
+            .collect()
+    }
+
+    /// Gets the blocking clause that excludes exactly this assignment. The
+    /// clause contains the negation of every assigned literal, variables that
+    /// are [`TernaryVal::DontCare`] are skipped.
+    pub fn as_clause(&self) -> Clause {
+        self.assignment
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, tv)| match tv {
+                TernaryVal::True => Some(var![idx as u32].neg_lit()),
+                TernaryVal::False => Some(var![idx as u32].pos_lit()),
+                TernaryVal::DontCare => None,
+            })
+            .collect()
+    }
 }
 
 impl fmt::Debug for Assignment {
@@ -749,6 +902,9 @@ pub enum TypeError {
     /// IPASIR index is zero
     #[error("zero is an invalid IPASIR literal")]
     IpasirZero,
+    /// A string could not be parsed as a variable or literal
+    #[error("could not parse `{0}` as a variable or literal")]
+    Parse(String),
 }
 
 /// An iterator over literals
@@ -772,7 +928,7 @@ impl<I: IntoIterator<Item = (Lit, isize)>> IWLitIter for I {}
 mod tests {
     use std::mem::size_of;
 
-    use super::{Assignment, Lit, TernaryVal, Var};
+    use super::{Assignment, Lit, TernaryVal, TypeError, Var};
 
     #[test]
     fn var_index() {
@@ -858,6 +1014,38 @@ mod tests {
         assert_eq!(lit.to_ipasir(), -(idx as i32 + 1));
     }
 
+    #[test]
+    fn ipasir_round_trip() {
+        for lit in [lit![0], !lit![0], lit![41], !lit![41]] {
+            assert_eq!(Lit::from_ipasir(lit.to_ipasir()).unwrap(), lit);
+        }
+        assert!(matches!(Lit::from_ipasir(0), Err(TypeError::IpasirZero)));
+    }
+
+    #[test]
+    fn parse_var() {
+        assert_eq!("x5".parse::<Var>().unwrap(), var![5]);
+        assert_eq!(var![7].to_string().parse::<Var>().unwrap(), var![7]);
+        assert!(matches!("5".parse::<Var>(), Err(TypeError::Parse(_))));
+        assert!(matches!("x".parse::<Var>(), Err(TypeError::Parse(_))));
+        assert!(matches!(
+            format!("x{}", u32::MAX).parse::<Var>(),
+            Err(TypeError::IdxTooHigh(..))
+        ));
+    }
+
+    #[test]
+    fn parse_lit() {
+        for lit in [lit![0], !lit![0], lit![12], !lit![12]] {
+            assert_eq!(lit.to_string().parse::<Lit>().unwrap(), lit);
+            assert_eq!(lit.to_ipasir().to_string().parse::<Lit>().unwrap(), lit);
+        }
+        assert_eq!("-4".parse::<Lit>().unwrap(), !lit![3]);
+        assert!(matches!("0".parse::<Lit>(), Err(TypeError::IpasirZero)));
+        assert!(matches!("~3".parse::<Lit>(), Err(TypeError::Parse(s)) if s == "~3"));
+        assert!(matches!("y".parse::<Lit>(), Err(TypeError::Parse(_))));
+    }
+
     #[test]
     fn ternary_var_true() {
         let tv = TernaryVal::True;
@@ -879,6 +1067,27 @@ mod tests {
         assert_eq!(tv.to_bool_with_def(false), false);
     }
 
+    #[test]
+    fn ternary_and_or() {
+        use TernaryVal::{DontCare as D, False as F, True as T};
+        let table = [
+            (T, T, T, T),
+            (T, F, F, T),
+            (T, D, D, T),
+            (F, T, F, T),
+            (F, F, F, F),
+            (F, D, F, D),
+            (D, T, D, T),
+            (D, F, F, D),
+            (D, D, D, D),
+        ];
+        for (a, b, and, or) in table {
+            assert_eq!(a.and(b), and);
+            assert_eq!(a.or(b), or);
+            assert_eq!(!a.and(b), (!a).or(!b));
+        }
+    }
+
     #[test]
     fn sol_var_val() {
         let sol = Assignment::from(vec![
@@ -956,4 +1165,37 @@ mod tests {
     fn ternary_val_size() {
         assert_eq!(size_of::<TernaryVal>(), 1);
     }
+
+    #[test]
+    fn assignment_project() {
+        let assign = Assignment::from(vec![TernaryVal::True, TernaryVal::False, TernaryVal::True]);
+        let proj = assign.project(&[Var::new(2), Var::new(0)]);
+        assert_eq!(proj.var_value(Var::new(0)), TernaryVal::True);
+        assert_eq!(proj.var_value(Var::new(1)), TernaryVal::DontCare);
+        assert_eq!(proj.var_value(Var::new(2)), TernaryVal::True);
+        assert_eq!(assign.project(&[]), Assignment::default());
+    }
+
+    #[test]
+    fn assignment_diff() {
+        let a = Assignment::from(vec![TernaryVal::True, TernaryVal::False, TernaryVal::True]);
+        let b = Assignment::from(vec![TernaryVal::True, TernaryVal::True]);
+        assert_eq!(a.diff(&b), vec![Var::new(1), Var::new(2)]);
+        assert_eq!(b.diff(&a), vec![Var::new(1), Var::new(2)]);
+        assert!(a.diff(&a).is_empty());
+    }
+
+    #[test]
+    fn assignment_as_clause() {
+        let assign = Assignment::from(vec![
+            TernaryVal::True,
+            TernaryVal::DontCare,
+            TernaryVal::False,
+        ]);
+        let cl = assign.as_clause();
+        assert_eq!(cl.len(), 2);
+        assert!(cl.iter().any(|&l| l == Lit::negative(0)));
+        assert!(cl.iter().any(|&l| l == Lit::positive(2)));
+        assert!(Assignment::default().as_clause().is_empty());
+    }
 }
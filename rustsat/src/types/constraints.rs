@@ -26,12 +26,31 @@ use pyo3::{
 /// Optional clauses as sets will be included in the future.
 #[cfg_attr(feature = "pyapi", pyclass)]
 #[derive(Eq, PartialOrd, Ord, Clone, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
 pub struct Clause {
     lits: Vec<Lit>,
     #[cfg(feature = "pyapi")]
+    #[cfg_attr(feature = "serde", serde(skip))]
     modified: bool,
 }
 
+/// The status of a normalized clause, see [`Clause::normalize_in_place`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClauseStatus {
+    /// The clause contains a literal and its negation
+    Tautology,
+    /// The clause consists of a single literal
+    Unit(Lit),
+    /// The clause is empty
+    Empty,
+    /// Any other clause
+    Normal,
+}
+
 impl PartialEq for Clause {
     fn eq(&self, other: &Self) -> bool {
         self.lits == other.lits
@@ -50,6 +69,21 @@ impl Clause {
         Self::default()
     }
 
+    /// Creates a new empty clause with a given capacity of literals
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            lits: Vec::with_capacity(capacity),
+            #[cfg(feature = "pyapi")]
+            modified: false,
+        }
+    }
+
+    /// Gets the capacity of the clause
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.lits.capacity()
+    }
+
     /// Gets the clause as a slice of literals
     pub fn lits(&self) -> &[Lit] {
         &self.lits
@@ -104,27 +138,41 @@ impl Clause {
     /// duplicates and removing the entire clause if it is a tautology.
     /// Comparing two normalized clauses checks their logical equivalence.
     pub fn normalize(mut self) -> Option<Self> {
-        if self.len() <= 1 {
-            return Some(self);
+        match self.normalize_in_place() {
+            ClauseStatus::Tautology => None,
+            _ => Some(self),
+        }
+    }
+
+    /// Normalizes the clause in place by sorting the literals and removing
+    /// duplicates, and returns the status of the normalized clause. A
+    /// tautological clause is sorted and deduplicated but not removed.
+    /// Normalizing an already normalized clause does not change it.
+    pub fn normalize_in_place(&mut self) -> ClauseStatus {
+        #[cfg(feature = "pyapi")]
+        {
+            self.modified = true;
         }
-        // Sort and filter duplicates
         self.lits.sort_unstable();
         self.lits.dedup();
-        // Check for tautology
-        let mut neg_last = None;
-        if let Err(()) = self.iter().try_for_each(|l| {
-            if let Some(neg_last) = neg_last {
-                if l == &neg_last {
-                    // Positive lits always come first
-                    return Err(());
-                }
-            }
-            neg_last = Some(!*l);
-            Ok(())
-        }) {
-            return None;
+        // Positive lits always come right before their negation
+        if self.lits.windows(2).any(|pair| pair[1] == !pair[0]) {
+            return ClauseStatus::Tautology;
         }
-        Some(self)
+        match self.lits[..] {
+            [] => ClauseStatus::Empty,
+            [unit] => ClauseStatus::Unit(unit),
+            _ => ClauseStatus::Normal,
+        }
+    }
+
+    /// Checks whether the clause contains a literal and its negation
+    pub fn is_tautology(&self) -> bool {
+        let mut seen = RsHashSet::default();
+        self.iter().any(|&l| {
+            seen.insert(l);
+            seen.contains(&!l)
+        })
     }
 
     /// Sanitizes the clause. This includes removing duplicates and removing the
@@ -726,7 +774,7 @@ pub enum PBToCardError {
 /// Type representing a pseudo-boolean constraint. When literals are added to a
 /// constraint, the constraint is transformed so that all coefficients are
 /// positive.
-#[derive(Eq, PartialEq, Clone, Debug)]
+#[derive(Hash, Eq, PartialEq, Clone, Debug)]
 pub enum PBConstraint {
     /// An upper bound pseudo-boolean constraint
     UB(PBUBConstr),
@@ -1030,7 +1078,7 @@ impl PBConstraint {
 }
 
 /// An upper bound pseudo-boolean constraint (`weighted sum of lits <= b`)
-#[derive(Eq, PartialEq, Clone, Debug)]
+#[derive(Hash, Eq, PartialEq, Clone, Debug)]
 pub struct PBUBConstr {
     lits: Vec<(Lit, usize)>,
     weight_sum: usize,
@@ -1100,7 +1148,7 @@ impl PBUBConstr {
 }
 
 /// A lower bound pseudo-boolean constraint (`weighted sum of lits >= b`)
-#[derive(Eq, PartialEq, Clone, Debug)]
+#[derive(Hash, Eq, PartialEq, Clone, Debug)]
 pub struct PBLBConstr {
     lits: Vec<(Lit, usize)>,
     weight_sum: usize,
@@ -1171,7 +1219,7 @@ impl PBLBConstr {
 }
 
 /// An equality pseudo-boolean constraint (`weighted sum of lits = b`)
-#[derive(Eq, PartialEq, Clone, Debug)]
+#[derive(Hash, Eq, PartialEq, Clone, Debug)]
 pub struct PBEQConstr {
     lits: Vec<(Lit, usize)>,
     weight_sum: usize,
@@ -1226,7 +1274,7 @@ impl PBEQConstr {
 
 #[cfg(test)]
 mod tests {
-    use super::{CardConstraint, PBConstraint};
+    use super::{CardConstraint, Clause, ClauseStatus, PBConstraint};
     use crate::{lit, types::Assignment, var};
 
     #[test]
@@ -1271,6 +1319,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn clause_normalize_in_place() {
+        let mut cl = clause![lit![3], !lit![1], lit![3], lit![0]];
+        assert_eq!(cl.normalize_in_place(), ClauseStatus::Normal);
+        assert_eq!(cl, clause![lit![0], !lit![1], lit![3]]);
+        let normalized = cl.clone();
+        assert_eq!(cl.normalize_in_place(), ClauseStatus::Normal);
+        assert_eq!(cl, normalized);
+
+        let mut cl = clause![!lit![2], lit![2], lit![1]];
+        assert!(cl.is_tautology());
+        assert_eq!(cl.normalize_in_place(), ClauseStatus::Tautology);
+        assert_eq!(cl.normalize_in_place(), ClauseStatus::Tautology);
+        assert_eq!(cl, clause![lit![1], lit![2], !lit![2]]);
+
+        let mut cl = clause![!lit![4], !lit![4]];
+        assert!(!cl.is_tautology());
+        assert_eq!(cl.normalize_in_place(), ClauseStatus::Unit(!lit![4]));
+        assert_eq!(cl.normalize_in_place(), ClauseStatus::Unit(!lit![4]));
+
+        let mut cl = Clause::new();
+        assert!(!cl.is_tautology());
+        assert_eq!(cl.normalize_in_place(), ClauseStatus::Empty);
+    }
+
     macro_rules! assign {
         ($val:expr) => {{
             let mut assign = Assignment::default();
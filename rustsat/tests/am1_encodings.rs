@@ -1,12 +1,13 @@
 use rustsat::{
-    encodings::am1::{Encode, Pairwise},
-    instances::{BasicVarManager, ManageVars},
+    clause,
+    encodings::am1::{Bimander, Commander, Encode, Ladder, Pairwise},
+    instances::{BasicVarManager, Cnf, ManageVars},
     lit,
     solvers::{
         Solve, SolveIncremental,
         SolverResult::{Sat, Unsat},
     },
-    types::Lit,
+    types::{Clause, Lit},
     var,
 };
 
@@ -38,3 +39,107 @@ fn test_am1<AM1: Encode + From<Vec<Lit>>>() {
 fn pairwise() {
     test_am1::<Pairwise>()
 }
+
+#[test]
+fn pairwise_exactly_one() {
+    let mut solver = rustsat_minisat::core::Minisat::default();
+    let mut var_manager = BasicVarManager::default();
+    var_manager.increase_next_free(var![3]);
+
+    let mut enc = Pairwise::from(vec![lit![0], lit![1], lit![2]]).with_exactly_one();
+    enc.encode(&mut solver, &mut var_manager).unwrap();
+
+    test_all!(
+        solver,
+        Vec::<Lit>::new(),
+        Unsat,
+        Unsat,
+        Unsat,
+        Sat,
+        Unsat,
+        Sat,
+        Sat,
+        Unsat
+    );
+}
+
+#[test]
+fn pairwise_exactly_one_tiny() {
+    let mut var_manager = BasicVarManager::default();
+
+    let mut cnf = Cnf::new();
+    let mut enc = Pairwise::from(vec![lit![0]]).with_exactly_one();
+    enc.encode(&mut cnf, &mut var_manager).unwrap();
+    assert_eq!(cnf.into_iter().collect::<Vec<_>>(), vec![clause![lit![0]]]);
+
+    let mut cnf = Cnf::new();
+    let mut enc = Pairwise::default().with_exactly_one();
+    enc.encode(&mut cnf, &mut var_manager).unwrap();
+    assert_eq!(cnf.into_iter().collect::<Vec<_>>(), vec![Clause::new()]);
+
+    let mut cnf = Cnf::new();
+    let mut enc = Pairwise::from(vec![lit![0]]);
+    enc.encode(&mut cnf, &mut var_manager).unwrap();
+    assert!(cnf.is_empty());
+}
+
+#[test]
+fn commander() {
+    test_am1::<Commander>()
+}
+
+/// Exhaustively checks the encoding over `n_lits` inputs
+fn test_am1_exhaustive<AM1: Encode>(n_lits: u32, make_enc: impl Fn(Vec<Lit>) -> AM1) {
+    let mut solver = rustsat_minisat::core::Minisat::default();
+    let mut var_manager = BasicVarManager::default();
+    var_manager.increase_next_free(var![n_lits]);
+
+    let lits: Vec<Lit> = (0..n_lits).map(|idx| lit![idx]).collect();
+    let mut enc = make_enc(lits.clone());
+    enc.encode(&mut solver, &mut var_manager).unwrap();
+
+    for bits in 0u32..1 << n_lits {
+        let assumps: Vec<Lit> = lits
+            .iter()
+            .enumerate()
+            .map(|(idx, &l)| if bits & (1 << idx) != 0 { l } else { !l })
+            .collect();
+        let expected = if bits.count_ones() <= 1 { Sat } else { Unsat };
+        assert_eq!(solver.solve_assumps(&assumps).unwrap(), expected);
+    }
+}
+
+#[test]
+fn bimander() {
+    test_am1::<Bimander>()
+}
+
+#[test]
+fn bimander_exhaustive() {
+    for n_lits in 4..6 {
+        for group_size in 1..4 {
+            test_am1_exhaustive(n_lits, |lits| {
+                Bimander::from(lits).with_group_size(group_size)
+            });
+        }
+    }
+}
+
+#[test]
+fn ladder() {
+    test_am1::<Ladder>()
+}
+
+#[test]
+fn ladder_exhaustive() {
+    for n_lits in 4..6 {
+        test_am1_exhaustive(n_lits, Ladder::from);
+    }
+}
+
+#[test]
+fn commander_groups() {
+    for group_size in 2..5 {
+        test_am1_exhaustive(7, |lits| Commander::from(lits).with_group_size(group_size));
+    }
+}
@@ -1,8 +1,10 @@
 use rustsat::{
     clause,
     encodings::card::{
+        self,
         simulators::{Double, Inverted},
-        BoundBoth, BoundBothIncremental, BoundUpperIncremental, DbTotalizer, Totalizer,
+        BoundBoth, BoundBothIncremental, BoundLowerIncremental, BoundUpperIncremental,
+        CardinalityNetwork, DbTotalizer, SequentialCounter, Totalizer,
     },
     instances::{BasicVarManager, ManageVars},
     lit,
@@ -10,7 +12,7 @@ use rustsat::{
         Solve, SolveIncremental,
         SolverResult::{self, Sat, Unsat},
     },
-    types::Lit,
+    types::{Lit, TernaryVal},
     var,
 };
 
@@ -138,6 +140,63 @@ fn test_inc_ub_card<CE: BoundUpperIncremental + Extend<Lit> + Default>() {
     assert_eq!(res, SolverResult::Sat);
 }
 
+fn test_inc_lb_card<CE: BoundLowerIncremental + Extend<Lit> + Default>() {
+    let mut solver = rustsat_minisat::core::Minisat::default();
+    let mut var_manager = BasicVarManager::default();
+    var_manager.increase_next_free(var![8]);
+
+    let mut enc = CE::default();
+    enc.extend(vec![lit![0], lit![1], lit![2]]);
+
+    enc.encode_lb(2..3, &mut solver, &mut var_manager);
+    let lb2 = enc.enforce_lb(2).unwrap();
+    let mut assumps = lb2.clone();
+    assumps.extend(vec![!lit![0], !lit![1]]);
+    let res = solver.solve_assumps(&assumps).unwrap();
+    assert_eq!(res, SolverResult::Unsat);
+    let mut assumps = lb2.clone();
+    assumps.push(!lit![0]);
+    let res = solver.solve_assumps(&assumps).unwrap();
+    assert_eq!(res, SolverResult::Sat);
+
+    enc.extend(vec![lit![3], lit![4]]);
+
+    enc.encode_lb_change(2..4, &mut solver, &mut var_manager);
+    // Previously returned assumptions still enforce the bound on the
+    // original literals
+    let mut assumps = lb2.clone();
+    assumps.extend(vec![!lit![0], !lit![1], lit![3], lit![4]]);
+    let res = solver.solve_assumps(&assumps).unwrap();
+    assert_eq!(res, SolverResult::Unsat);
+    let mut assumps = enc.enforce_lb(2).unwrap();
+    assumps.extend(vec![!lit![0], !lit![1], lit![3]]);
+    let res = solver.solve_assumps(&assumps).unwrap();
+    assert_eq!(res, SolverResult::Sat);
+    let mut assumps = enc.enforce_lb(3).unwrap();
+    assumps.extend(vec![!lit![0], !lit![1], !lit![2]]);
+    let res = solver.solve_assumps(&assumps).unwrap();
+    assert_eq!(res, SolverResult::Unsat);
+    let mut assumps = enc.enforce_lb(3).unwrap();
+    assumps.extend(vec![!lit![0], !lit![1]]);
+    let res = solver.solve_assumps(&assumps).unwrap();
+    assert_eq!(res, SolverResult::Sat);
+
+    enc.extend(vec![lit![5], lit![6], lit![7]]);
+
+    enc.encode_lb_change(0..7, &mut solver, &mut var_manager);
+    let mut assumps = enc.enforce_lb(6).unwrap();
+    assumps.extend(vec![!lit![0], !lit![1]]);
+    let res = solver.solve_assumps(&assumps).unwrap();
+    assert_eq!(res, SolverResult::Sat);
+    assumps.push(!lit![7]);
+    let res = solver.solve_assumps(&assumps).unwrap();
+    assert_eq!(res, SolverResult::Unsat);
+    let mut assumps = lb2;
+    assumps.extend(vec![!lit![0], !lit![1]]);
+    let res = solver.solve_assumps(&assumps).unwrap();
+    assert_eq!(res, SolverResult::Unsat);
+}
+
 fn test_both_card<CE: BoundBoth + From<Vec<Lit>>>() {
     // Set up instance
     let mut solver = rustsat_minisat::core::Minisat::default();
@@ -246,6 +305,31 @@ fn tot_min_enc() {
     test_both_card_min_enc::<Totalizer>()
 }
 
+#[test]
+fn cardnet_inc_both() {
+    test_inc_both_card::<CardinalityNetwork>()
+}
+
+#[test]
+fn cardnet_both() {
+    test_both_card::<CardinalityNetwork>()
+}
+
+#[test]
+fn cardnet_min_enc() {
+    test_both_card_min_enc::<CardinalityNetwork>()
+}
+
+#[test]
+fn seqcounter_both() {
+    test_both_card::<Double<SequentialCounter, Inverted<SequentialCounter>>>()
+}
+
+#[test]
+fn seqcounter_min_enc() {
+    test_both_card_min_enc::<Double<SequentialCounter, Inverted<SequentialCounter>>>()
+}
+
 #[test]
 fn invertet_tot() {
     test_inc_both_card::<Inverted<Totalizer>>()
@@ -261,6 +345,11 @@ fn tot_inc_ub() {
     test_inc_ub_card::<Totalizer>()
 }
 
+#[test]
+fn tot_inc_lb() {
+    test_inc_lb_card::<Totalizer>()
+}
+
 #[test]
 fn dbtot_inc_ub() {
     test_inc_ub_card::<DbTotalizer>()
@@ -531,3 +620,60 @@ fn invtot_both_exhaustive() {
 fn dbtot_ub_exhaustive() {
     test_ub_exhaustive::<DbTotalizer>()
 }
+
+#[test]
+fn cardnet_ub_exhaustive() {
+    test_ub_exhaustive::<CardinalityNetwork>()
+}
+
+#[test]
+fn cardnet_both_exhaustive() {
+    test_both_exhaustive::<CardinalityNetwork>()
+}
+
+#[test]
+fn one_shot_helpers() {
+    let lits = vec![lit![0], lit![1], lit![2]];
+    let n = lits.len();
+    let mut var_manager = BasicVarManager::default();
+    var_manager.increase_next_free(var![3]);
+    let mut solver = rustsat_minisat::core::Minisat::default();
+    solver.add_unit(lit![0]).unwrap();
+    solver.add_unit(lit![1]).unwrap();
+
+    // k = 0
+    let (cnf, assumps) = card::encode_atmost_k(lits.clone(), 0, &mut var_manager).unwrap();
+    solver.add_cnf(cnf).unwrap();
+    assert_eq!(solver.solve_assumps(&assumps).unwrap(), Unsat);
+    let (cnf, assumps) = card::encode_atleast_k(lits.clone(), 0, &mut var_manager).unwrap();
+    assert!(cnf.is_empty() && assumps.is_empty());
+    let (cnf, assumps) = card::encode_exactly_k(lits.clone(), 0, &mut var_manager).unwrap();
+    solver.add_cnf(cnf).unwrap();
+    assert_eq!(solver.solve_assumps(&assumps).unwrap(), Unsat);
+
+    // 0 < k < n
+    let (cnf, assumps) = card::encode_atmost_k(lits.clone(), 2, &mut var_manager).unwrap();
+    solver.add_cnf(cnf).unwrap();
+    let mut all_assumps = assumps;
+    all_assumps.push(lit![2]);
+    assert_eq!(solver.solve_assumps(&all_assumps).unwrap(), Unsat);
+    let (cnf, assumps) = card::encode_exactly_k(lits.clone(), 2, &mut var_manager).unwrap();
+    solver.add_cnf(cnf).unwrap();
+    assert_eq!(solver.solve_assumps(&assumps).unwrap(), Sat);
+    assert_eq!(solver.lit_val(lit![2]).unwrap(), TernaryVal::False);
+
+    // k = n
+    let (cnf, assumps) = card::encode_atmost_k(lits.clone(), n, &mut var_manager).unwrap();
+    assert!(cnf.is_empty() && assumps.is_empty());
+    let (cnf, assumps) = card::encode_atleast_k(lits.clone(), n, &mut var_manager).unwrap();
+    solver.add_cnf(cnf).unwrap();
+    assert_eq!(solver.solve_assumps(&assumps).unwrap(), Sat);
+    assert_eq!(solver.lit_val(lit![2]).unwrap(), TernaryVal::True);
+    let (cnf, assumps) = card::encode_exactly_k(lits.clone(), n, &mut var_manager).unwrap();
+    solver.add_cnf(cnf).unwrap();
+    assert_eq!(solver.solve_assumps(&assumps).unwrap(), Sat);
+
+    // k > n
+    assert!(card::encode_atleast_k(lits.clone(), n + 1, &mut var_manager).is_err());
+    assert!(card::encode_exactly_k(lits, n + 1, &mut var_manager).is_err());
+}
@@ -1,11 +1,12 @@
 use rustsat::{
-    instances::Cnf,
+    instances::{BasicVarManager, Cnf, ManageVars, SatInstance},
     lit,
     solvers::{
         Solve, SolveIncremental,
         SolverResult::{Sat, Unsat},
     },
-    types::Lit,
+    types::{constraints::CardConstraint, Assignment, Lit},
+    var,
 };
 use rustsat_tools::{test_all, test_assignment};
 
@@ -185,3 +186,43 @@ fn cnf_implications() {
         Sat                // 0000
     );
 }
+
+#[test]
+fn cnf_equivalence() {
+    let mut cnf = Cnf::new();
+    cnf.add_equiv(lit![0], lit![1]);
+    let mut solver = rustsat_minisat::core::Minisat::default();
+    solver.add_cnf(cnf).unwrap();
+    test_all!(solver, Vec::<Lit>::new(), Sat, Unsat, Unsat, Sat);
+}
+
+#[test]
+fn cnf_from_instance() {
+    let mut instance: SatInstance = SatInstance::new();
+    instance.add_card_constr(CardConstraint::new_ub(vec![lit![0], lit![1], lit![2]], 1));
+    instance.add_binary(lit![0], lit![1]);
+    let mut var_manager = BasicVarManager::default();
+    let cnf = Cnf::try_from_instance(&instance, &mut var_manager).unwrap();
+    assert!(var_manager.n_used() >= 3);
+    let mut solver = rustsat_minisat::core::Minisat::default();
+    solver.add_cnf(cnf).unwrap();
+    assert_eq!(solver.solve_assumps(&[lit![0], lit![1]]).unwrap(), Unsat);
+    assert_eq!(solver.solve_assumps(&[lit![0], lit![2]]).unwrap(), Unsat);
+    assert_eq!(solver.solve_assumps(&[!lit![0], !lit![1]]).unwrap(), Unsat);
+    assert_eq!(solver.solve_assumps(&[lit![0]]).unwrap(), Sat);
+    assert_eq!(solver.solve_assumps(&[lit![1]]).unwrap(), Sat);
+}
+
+#[test]
+fn cnf_satisfied_by_solver_model() {
+    let mut cnf = Cnf::new();
+    cnf.add_binary(lit![0], lit![1]);
+    cnf.add_binary(!lit![0], lit![2]);
+    cnf.add_unit(!lit![1]);
+    let mut solver = rustsat_minisat::core::Minisat::default();
+    solver.add_cnf(cnf.clone()).unwrap();
+    assert_eq!(solver.solve().unwrap(), Sat);
+    let vars = (0..3).map(|idx| var![idx]);
+    let assign = Assignment::from_solver(&solver, vars).unwrap();
+    assert!(cnf.is_satisfied(&assign));
+}
@@ -1,5 +1,5 @@
 use rustsat::{
-    instances::{BasicVarManager, SatInstance},
+    instances::{BasicVarManager, Cnf, SatInstance},
     solvers::Solve,
     solvers::SolverResult,
 };
@@ -49,3 +49,57 @@ fn small_unsat_instance_bz2() {
     let res = solver.solve().unwrap();
     assert_eq!(res, SolverResult::Unsat);
 }
+
+#[test]
+fn gzip_detected_without_extension() {
+    let path = std::env::temp_dir().join("rustsat-gzip-sniff.cnf");
+    std::fs::copy("./data/AProVE11-12.cnf.gz", &path).unwrap();
+    let sniffed = Cnf::from_dimacs_path(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    let plain = Cnf::from_dimacs_path("./data/AProVE11-12.cnf").unwrap();
+    assert_eq!(sniffed, plain);
+}
+
+#[test]
+fn plain_file_with_gz_extension() {
+    let path = std::env::temp_dir().join("rustsat-plain-named.cnf.gz");
+    std::fs::copy("./data/AProVE11-12.cnf", &path).unwrap();
+    let parsed = Cnf::from_dimacs_path(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    let plain = Cnf::from_dimacs_path("./data/AProVE11-12.cnf").unwrap();
+    assert_eq!(parsed, plain);
+}
+
+#[test]
+fn bz2_detected_without_extension() {
+    let path = std::env::temp_dir().join("rustsat-bz2-sniff.cnf");
+    std::fs::copy("./data/AProVE11-12.cnf.bz2", &path).unwrap();
+    let sniffed = Cnf::from_dimacs_path(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    let plain = Cnf::from_dimacs_path("./data/AProVE11-12.cnf").unwrap();
+    assert_eq!(sniffed, plain);
+}
+
+#[cfg(unix)]
+#[test]
+fn gzip_detected_in_fifo() {
+    let path = std::env::temp_dir().join("rustsat-gzip-fifo.cnf");
+    let _ = std::fs::remove_file(&path);
+    assert!(std::process::Command::new("mkfifo")
+        .arg(&path)
+        .status()
+        .unwrap()
+        .success());
+    let writer = {
+        let path = path.clone();
+        std::thread::spawn(move || {
+            let data = std::fs::read("./data/AProVE11-12.cnf.gz").unwrap();
+            std::fs::write(path, data).unwrap();
+        })
+    };
+    let sniffed = Cnf::from_dimacs_path(&path).unwrap();
+    writer.join().unwrap();
+    std::fs::remove_file(&path).unwrap();
+    let plain = Cnf::from_dimacs_path("./data/AProVE11-12.cnf").unwrap();
+    assert_eq!(sniffed, plain);
+}
@@ -32,6 +32,14 @@ fn opb_tiny_unsat() {
     opb_test!("./data/tiny-unsat.opb", SolverResult::Unsat);
 }
 
+#[test]
+fn opb_tiny_unsat_propagates_to_conflict() {
+    let inst: SatInstance =
+        SatInstance::from_opb_path("./data/tiny-unsat.opb", Options::default()).unwrap();
+    let (mut cnf, _) = inst.as_cnf();
+    assert_eq!(cnf.propagate_units(), None);
+}
+
 #[test]
 fn opb_opt() {
     let inst: OptInstance =
@@ -0,0 +1,122 @@
+use rustsat::{
+    clause,
+    instances::{lex_optimize, solve_maxsat, BasicVarManager, ManageVars},
+    lit,
+    solvers::{Solve, SolverResult},
+    types::{RsHashMap, TernaryVal},
+    var,
+};
+
+#[test]
+fn lex_optimize_fixes_earlier_optima() {
+    let mut solver = rustsat_minisat::core::Minisat::default();
+    let mut var_manager = BasicVarManager::default();
+    var_manager.increase_next_free(var![4]);
+    solver
+        .add_clause(clause![lit![0], lit![1], lit![2]])
+        .unwrap();
+    solver.add_binary(lit![2], lit![3]).unwrap();
+    // the second objective prefers lit 2, but only at the expense of the first
+    let first = RsHashMap::from_iter([(lit![0], 3), (lit![1], 3), (lit![2], 4)]);
+    let second = RsHashMap::from_iter([(lit![0], 2), (lit![1], 1), (lit![3], 5)]);
+    let optima = lex_optimize(&mut solver, vec![first, second], &mut var_manager).unwrap();
+    assert_eq!(optima, Some(vec![3, 6]));
+    // the optima stay fixed for later calls
+    assert_eq!(solver.solve().unwrap(), SolverResult::Sat);
+    assert_eq!(solver.lit_val(lit![1]).unwrap(), TernaryVal::True);
+    assert_eq!(solver.lit_val(lit![2]).unwrap(), TernaryVal::False);
+    assert_eq!(solver.lit_val(lit![3]).unwrap(), TernaryVal::True);
+}
+
+#[test]
+fn lex_optimize_unsat() {
+    let mut solver = rustsat_minisat::core::Minisat::default();
+    let mut var_manager = BasicVarManager::default();
+    var_manager.increase_next_free(var![1]);
+    solver.add_unit(lit![0]).unwrap();
+    solver.add_unit(!lit![0]).unwrap();
+    let obj = RsHashMap::from_iter([(lit![0], 1)]);
+    assert_eq!(
+        lex_optimize(&mut solver, vec![obj], &mut var_manager).unwrap(),
+        None
+    );
+}
+
+#[test]
+fn maxsat_weighted() {
+    let mut solver = rustsat_minisat::core::Minisat::default();
+    let mut var_manager = BasicVarManager::default();
+    var_manager.increase_next_free(var![3]);
+    solver.add_binary(!lit![0], !lit![1]).unwrap();
+    solver.add_binary(!lit![1], !lit![2]).unwrap();
+    solver.add_binary(!lit![0], !lit![2]).unwrap();
+    let softs = vec![
+        (clause![lit![0]], 4),
+        (clause![lit![1]], 3),
+        (clause![lit![2]], 3),
+        (clause![lit![1], lit![2]], 5),
+    ];
+    let (cost, sol) = solve_maxsat(&mut solver, softs, &mut var_manager)
+        .unwrap()
+        .unwrap();
+    assert_eq!(cost, 7);
+    assert_eq!(sol.lit_value(lit![0]), TernaryVal::False);
+}
+
+#[test]
+fn maxsat_unweighted() {
+    let mut solver = rustsat_minisat::core::Minisat::default();
+    let mut var_manager = BasicVarManager::default();
+    var_manager.increase_next_free(var![4]);
+    // at most one of the four literals can be true
+    for i in 0..4 {
+        for j in i + 1..4 {
+            solver.add_binary(!lit![i], !lit![j]).unwrap();
+        }
+    }
+    let softs = (0..4).map(|i| (clause![lit![i]], 1)).collect();
+    let (cost, sol) = solve_maxsat(&mut solver, softs, &mut var_manager)
+        .unwrap()
+        .unwrap();
+    assert_eq!(cost, 3);
+    assert_eq!(
+        (0..4)
+            .filter(|&i| sol.lit_value(lit![i]) == TernaryVal::True)
+            .count(),
+        1
+    );
+}
+
+#[test]
+fn maxsat_unweighted_repeated_softs() {
+    let mut solver = rustsat_minisat::core::Minisat::default();
+    let mut var_manager = BasicVarManager::default();
+    var_manager.increase_next_free(var![3]);
+    solver.add_binary(!lit![0], !lit![1]).unwrap();
+    solver.add_unit(!lit![2]).unwrap();
+    let softs = vec![
+        (clause![lit![0]], 1),
+        (clause![lit![0]], 1),
+        (clause![lit![0]], 1),
+        (clause![lit![1]], 1),
+        (clause![lit![1], lit![2]], 1),
+    ];
+    let (cost, sol) = solve_maxsat(&mut solver, softs, &mut var_manager)
+        .unwrap()
+        .unwrap();
+    assert_eq!(cost, 2);
+    assert_eq!(sol.lit_value(lit![0]), TernaryVal::True);
+}
+
+#[test]
+fn maxsat_unsat() {
+    let mut solver = rustsat_minisat::core::Minisat::default();
+    let mut var_manager = BasicVarManager::default();
+    var_manager.increase_next_free(var![1]);
+    solver.add_unit(lit![0]).unwrap();
+    solver.add_unit(!lit![0]).unwrap();
+    let softs = vec![(clause![lit![0]], 1)];
+    assert!(solve_maxsat(&mut solver, softs, &mut var_manager)
+        .unwrap()
+        .is_none());
+}
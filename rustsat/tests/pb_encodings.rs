@@ -1,12 +1,14 @@
 use rustsat::{
     clause,
     encodings::{
-        card::Totalizer,
+        card::{self, Totalizer},
         pb::{
-            simulators::Card, BoundBoth, BoundBothIncremental, BoundLower, BoundUpper,
-            BoundUpperIncremental, DbGte, DoubleGeneralizedTotalizer, DynamicPolyWatchdog,
-            GeneralizedTotalizer, InvertedGeneralizedTotalizer,
+            self, simulators::Card, AdderNetwork, BddPb, BoundBoth, BoundBothIncremental,
+            BoundLower, BoundUpper, BoundUpperIncremental, DbGte, DoubleGeneralizedTotalizer,
+            DynamicPolyWatchdog, Encode, GeneralizedTotalizer, InvertedGeneralizedTotalizer,
+            PolynomialWatchdog, SorterPb,
         },
+        IterWeightedInputs,
     },
     instances::{BasicVarManager, ManageVars},
     lit,
@@ -152,6 +154,62 @@ fn test_pb_eq<PBE: BoundBothIncremental + From<RsHashMap<Lit, usize>>>() {
     assert_eq!(res, SolverResult::Unsat);
 }
 
+fn check_pb_eq<S: SolveIncremental, PBE: BoundBoth>(
+    solver: &mut S,
+    enc: &PBE,
+    weights: &[usize],
+    b: usize,
+) {
+    for assign in 0..1_usize << weights.len() {
+        let mut assumps = enc.enforce_eq(b).unwrap();
+        let mut sum = 0;
+        for (idx, &w) in weights.iter().enumerate() {
+            if (assign >> idx) & 1 == 1 {
+                assumps.push(lit![idx as u32]);
+                sum += w;
+            } else {
+                assumps.push(!lit![idx as u32]);
+            }
+        }
+        let expected = if sum == b { Sat } else { Unsat };
+        let res = solver.solve_assumps(&assumps).unwrap();
+        assert_eq!(res, expected);
+    }
+}
+
+fn test_inc_pb_eq<
+    PBE: BoundBothIncremental + From<RsHashMap<Lit, usize>> + Extend<(Lit, usize)>,
+>() {
+    // Set up instance
+    let mut solver = rustsat_minisat::core::Minisat::default();
+    let mut var_manager = BasicVarManager::default();
+    var_manager.increase_next_free(var![4]);
+
+    let mut weights = vec![4, 2, 2];
+    let mut lits = RsHashMap::default();
+    lits.insert(lit![0], 4);
+    lits.insert(lit![1], 2);
+    lits.insert(lit![2], 2);
+    let mut enc = PBE::from(lits);
+
+    enc.encode_both_change(4..5, &mut solver, &mut var_manager);
+    check_pb_eq(&mut solver, &enc, &weights, 4);
+
+    // Change the target value
+    enc.encode_both_change(2..3, &mut solver, &mut var_manager);
+    check_pb_eq(&mut solver, &enc, &weights, 2);
+    enc.encode_both_change(6..7, &mut solver, &mut var_manager);
+    check_pb_eq(&mut solver, &enc, &weights, 6);
+
+    // Add a literal and change the target again
+    enc.extend(vec![(lit![3], 3)]);
+    weights.push(3);
+    enc.encode_both_change(5..6, &mut solver, &mut var_manager);
+    check_pb_eq(&mut solver, &enc, &weights, 5);
+    enc.encode_both_change(7..8, &mut solver, &mut var_manager);
+    check_pb_eq(&mut solver, &enc, &weights, 7);
+}
+
 fn test_pb_lb<PBE: BoundLower + From<RsHashMap<Lit, usize>>>() {
     // Set up instance
     let mut solver = rustsat_minisat::core::Minisat::default();
@@ -235,6 +293,33 @@ fn test_pb_ub_min_enc<PBE: BoundUpper + From<RsHashMap<Lit, usize>>>() {
     assert_eq!(res, SolverResult::Sat);
 }
 
+/// Checks an encoding over `lit![0]` with accumulated weight 5 and a
+/// zero-weight `lit![1]` that must have been dropped
+fn check_accumulated_weights<PBE: BoundUpper>(mut enc: PBE) {
+    assert_eq!(enc.weight_sum(), 5);
+    let mut solver = rustsat_minisat::core::Minisat::default();
+    let mut var_manager = BasicVarManager::default();
+    var_manager.increase_next_free(var![2]);
+    enc.encode_ub(0..=5, &mut solver, &mut var_manager);
+    for ub in 0..=5 {
+        let mut assumps = enc.enforce_ub(ub).unwrap();
+        assumps.push(lit![0]);
+        let expected = if ub < 5 { Unsat } else { Sat };
+        assert_eq!(solver.solve_assumps(&assumps).unwrap(), expected);
+    }
+}
+
+fn test_pb_accumulate_weights<PBE>()
+where
+    PBE: BoundUpper + FromIterator<(Lit, usize)> + Extend<(Lit, usize)> + Default,
+{
+    check_accumulated_weights(PBE::from_iter([(lit![0], 2), (lit![0], 3), (lit![1], 0)]));
+    let mut enc = PBE::default();
+    enc.extend([(lit![0], 2)]);
+    enc.extend([(lit![0], 3), (lit![1], 0)]);
+    check_accumulated_weights(enc);
+}
+
 #[test]
 fn gte_ub() {
     test_inc_pb_ub::<GeneralizedTotalizer>()
@@ -255,6 +340,11 @@ fn gte_eq() {
     test_pb_eq::<DoubleGeneralizedTotalizer>()
 }
 
+#[test]
+fn gte_inc_eq() {
+    test_inc_pb_eq::<DoubleGeneralizedTotalizer>()
+}
+
 #[test]
 fn tot_pb_sim_eq() {
     test_pb_eq::<Card<Totalizer>>()
@@ -275,6 +365,146 @@ fn dbgte_min_enc() {
     test_pb_ub_min_enc::<DbGte>()
 }
 
+#[test]
+fn sorter_min_enc() {
+    test_pb_ub_min_enc::<SorterPb>()
+}
+
+#[test]
+fn bdd_min_enc() {
+    test_pb_ub_min_enc::<BddPb>()
+}
+
+#[test]
+fn adder_min_enc() {
+    test_pb_ub_min_enc::<AdderNetwork>()
+}
+
+#[test]
+fn pw_min_enc() {
+    test_pb_ub_min_enc::<PolynomialWatchdog>()
+}
+
+#[test]
+fn dpw_accumulate_weights() {
+    check_accumulated_weights(DynamicPolyWatchdog::from_iter([
+        (lit![0], 2),
+        (lit![0], 3),
+        (lit![1], 0),
+    ]));
+}
+
+#[test]
+fn dbgte_accumulate_weights() {
+    test_pb_accumulate_weights::<DbGte>()
+}
+
+#[test]
+fn sorter_accumulate_weights() {
+    test_pb_accumulate_weights::<SorterPb>()
+}
+
+#[test]
+fn bdd_accumulate_weights() {
+    test_pb_accumulate_weights::<BddPb>()
+}
+
+#[test]
+fn adder_accumulate_weights() {
+    test_pb_accumulate_weights::<AdderNetwork>()
+}
+
+#[test]
+fn pw_accumulate_weights() {
+    test_pb_accumulate_weights::<PolynomialWatchdog>()
+}
+
+#[test]
+fn pw_large_coefficients() {
+    let mut solver = rustsat_minisat::core::Minisat::default();
+    let mut var_manager = BasicVarManager::default();
+    var_manager.increase_next_free(var![3]);
+
+    let mut enc =
+        PolynomialWatchdog::from_iter([(lit![0], 1), (lit![1], 1_000), (lit![2], 1_000_000)]);
+    enc.encode_ub(1_000..1_002, &mut solver, &mut var_manager);
+
+    let mut assumps = enc.enforce_ub(1_000).unwrap();
+    assumps.extend([lit![0], lit![1]]);
+    assert_eq!(solver.solve_assumps(&assumps).unwrap(), Unsat);
+
+    let mut assumps = enc.enforce_ub(1_001).unwrap();
+    assumps.extend([lit![0], lit![1], !lit![2]]);
+    assert_eq!(solver.solve_assumps(&assumps).unwrap(), Sat);
+
+    let mut assumps = enc.enforce_ub(1_001).unwrap();
+    assumps.push(lit![2]);
+    assert_eq!(solver.solve_assumps(&assumps).unwrap(), Unsat);
+}
+
+#[test]
+fn adder_extend() {
+    let mut solver = rustsat_minisat::core::Minisat::default();
+    let mut var_manager = BasicVarManager::default();
+    var_manager.increase_next_free(var![4]);
+
+    let mut weights = vec![5, 3, 6];
+    let mut enc = AdderNetwork::from_iter(
+        weights
+            .iter()
+            .enumerate()
+            .map(|(idx, &w)| (lit![idx as u32], w)),
+    );
+    enc.encode_ub(0..enc.weight_sum(), &mut solver, &mut var_manager);
+    enc.extend([(lit![3], 7)]);
+    weights.push(7);
+    enc.encode_ub(0..enc.weight_sum(), &mut solver, &mut var_manager);
+
+    for ub in 0..enc.weight_sum() {
+        let enforce = enc.enforce_ub(ub).unwrap();
+        for bits in 0u32..1 << 4 {
+            let mut assumps = enforce.clone();
+            let mut sum = 0;
+            for (idx, &w) in weights.iter().enumerate() {
+                if bits & (1 << idx) != 0 {
+                    assumps.push(lit![idx as u32]);
+                    sum += w;
+                } else {
+                    assumps.push(!lit![idx as u32]);
+                }
+            }
+            let expected = if sum <= ub { Sat } else { Unsat };
+            assert_eq!(solver.solve_assumps(&assumps).unwrap(), expected);
+        }
+    }
+}
+
+#[test]
+fn cumulative_capacity() {
+    let mut solver = rustsat_minisat::core::Minisat::default();
+    let mut var_manager = BasicVarManager::default();
+    var_manager.increase_next_free(var![4]);
+
+    let tasks = [(lit![0], 3), (lit![1], 4), (lit![2], 2), (lit![3], 5)];
+    let encoding = pb::cumulative(&tasks, 8, &mut var_manager);
+    solver.add_cnf(encoding).unwrap();
+
+    // Demand 3 + 4 = 7 stays within capacity
+    let res = solver
+        .solve_assumps(&[lit![0], lit![1], !lit![2], !lit![3]])
+        .unwrap();
+    assert_eq!(res, SolverResult::Sat);
+    // Demand 3 + 5 = 8 fully uses capacity
+    let res = solver.solve_assumps(&[lit![0], lit![3]]).unwrap();
+    assert_eq!(res, SolverResult::Sat);
+    // Demand 3 + 4 + 2 = 9 exceeds capacity
+    let res = solver.solve_assumps(&[lit![0], lit![1], lit![2]]).unwrap();
+    assert_eq!(res, SolverResult::Unsat);
+    // Demand 4 + 5 = 9 exceeds capacity
+    let res = solver.solve_assumps(&[lit![1], lit![3]]).unwrap();
+    assert_eq!(res, SolverResult::Unsat);
+}
+
 use rustsat_tools::{test_all, test_assignment};
 
 fn test_ub_exhaustive<PBE: BoundUpperIncremental + From<RsHashMap<Lit, usize>>>(
@@ -415,3 +645,63 @@ generate_exhaustive!(
     tot_sim,
     simulators::Card<rustsat::encodings::card::Totalizer>
 );
+
+fn test_ub_exhaustive_non_inc<PBE: BoundUpper + From<RsHashMap<Lit, usize>>>(weights: &[usize]) {
+    let mut solver = rustsat_minisat::core::Minisat::default();
+    let mut lits = RsHashMap::default();
+    for (idx, &w) in weights.iter().enumerate() {
+        lits.insert(lit![idx as u32], w);
+    }
+    let mut enc = PBE::from(lits);
+    let mut var_manager = BasicVarManager::default();
+    var_manager.increase_next_free(var![weights.len() as u32]);
+
+    let max_val = weights.iter().sum();
+    enc.encode_ub(0..=max_val, &mut solver, &mut var_manager);
+    for bound in 0..=max_val {
+        let enforced = enc.enforce_ub(bound).unwrap();
+        for assign in 0..1_usize << weights.len() {
+            let mut assumps = enforced.clone();
+            let mut sum = 0;
+            for (idx, &w) in weights.iter().enumerate() {
+                if (assign >> idx) & 1 == 1 {
+                    assumps.push(lit![idx as u32]);
+                    sum += w;
+                } else {
+                    assumps.push(!lit![idx as u32]);
+                }
+            }
+            let expected = if sum <= bound { Sat } else { Unsat };
+            let res = solver.solve_assumps(&assumps).unwrap();
+            assert_eq!(res, expected);
+        }
+    }
+}
+
+#[test]
+fn sorter_exhaustive_mixed() {
+    test_ub_exhaustive_non_inc::<SorterPb>(&[1, 1, 1, 1]);
+    test_ub_exhaustive_non_inc::<SorterPb>(&[7, 7, 7, 7]);
+    test_ub_exhaustive_non_inc::<SorterPb>(&[5, 5, 3, 3]);
+    test_ub_exhaustive_non_inc::<SorterPb>(&[2, 1, 7, 3]);
+    test_ub_exhaustive_non_inc::<SorterPb>(&[8, 9, 1, 8]);
+    test_ub_exhaustive_non_inc::<SorterPb>(&[3, 6, 11, 4, 9]);
+    test_ub_exhaustive_non_inc::<SorterPb>(&[13, 13, 2, 1, 6, 5]);
+}
+
+#[test]
+fn card_pb_round_trip() {
+    let lits = vec![lit![0], lit![1], lit![2], lit![3]];
+    let weights = card::card_to_pb(&Totalizer::from(lits.clone()));
+    assert_eq!(weights.len(), 4);
+    assert!(weights.values().all(|&w| w == 1));
+    assert!(pb::pb_is_cardinality(&weights));
+    let enc = GeneralizedTotalizer::from(weights.clone());
+    let mut back: Vec<Lit> = enc.iter().map(|(l, _)| l).collect();
+    back.sort();
+    assert_eq!(back, lits);
+
+    let mut weights = weights;
+    weights.insert(lit![4], 2);
+    assert!(!pb::pb_is_cardinality(&weights));
+}
@@ -0,0 +1,429 @@
+use rustsat::{
+    clause,
+    instances::{BasicVarManager, ManageVars, SatInstance},
+    lit,
+    solvers::{
+        compute_backbone, minimize_core, verify_result, ClauseRecorder, ClauseStack,
+        CoreMinimization, LimitPropagations, PropagateQuery, QuerySession, RemovableClauses, Solve,
+        SolveIncremental, SolveStats, SolverError, SolverResult, VerificationError,
+    },
+    types::TernaryVal,
+    var,
+};
+
+#[test]
+fn clause_stack_push_pop() {
+    let mut var_manager = BasicVarManager::default();
+    var_manager.increase_next_free(var![2]);
+    let mut stack = ClauseStack::new(rustsat_minisat::core::Minisat::default());
+    stack.add_binary(lit![0], lit![1]).unwrap();
+    assert_eq!(stack.solve().unwrap(), SolverResult::Sat);
+    stack
+        .push_clause(clause![!lit![0]], &mut var_manager)
+        .unwrap();
+    assert_eq!(stack.solve().unwrap(), SolverResult::Sat);
+    stack
+        .push_clause(clause![!lit![1]], &mut var_manager)
+        .unwrap();
+    assert_eq!(stack.depth(), 2);
+    assert_eq!(stack.solve().unwrap(), SolverResult::Unsat);
+    assert_eq!(
+        stack.solve_assumps(&[lit![0]]).unwrap(),
+        SolverResult::Unsat
+    );
+    assert!(stack.core().unwrap().iter().all(|&l| l == !lit![0]));
+    assert!(stack.pop_clause().unwrap());
+    assert_eq!(stack.solve().unwrap(), SolverResult::Sat);
+    assert_eq!(stack.lit_val(lit![1]).unwrap(), TernaryVal::True);
+    assert!(stack.pop_clause().unwrap());
+    assert!(!stack.pop_clause().unwrap());
+    assert_eq!(stack.solve_assumps(&[lit![0]]).unwrap(), SolverResult::Sat);
+}
+
+#[test]
+fn removable_clause_groups() {
+    let mut var_manager = BasicVarManager::default();
+    var_manager.increase_next_free(var![2]);
+    let mut solver = rustsat_minisat::core::Minisat::default();
+    let mut removable = RemovableClauses::default();
+    solver.add_binary(lit![0], lit![1]).unwrap();
+    removable
+        .add_clause(0, clause![!lit![0]], &mut solver, &mut var_manager)
+        .unwrap();
+    removable
+        .add_clause(1, clause![!lit![1]], &mut solver, &mut var_manager)
+        .unwrap();
+    assert_eq!(removable.n_groups(), 2);
+    let g0 = removable.activate(0).unwrap();
+    let g1 = removable.activate(1).unwrap();
+    assert_ne!(g0, g1);
+    assert_eq!(
+        solver.solve_assumps(&[g0, g1]).unwrap(),
+        SolverResult::Unsat
+    );
+    assert_eq!(solver.solve_assumps(&[g0]).unwrap(), SolverResult::Sat);
+    assert_eq!(solver.lit_val(lit![1]).unwrap(), TernaryVal::True);
+    assert_eq!(removable.deactivate(1, &mut solver).unwrap(), Some(!g1));
+    assert_eq!(removable.deactivate(1, &mut solver).unwrap(), None);
+    assert_eq!(removable.activate(1), None);
+    assert_eq!(
+        solver.solve_assumps(&removable.assumps()).unwrap(),
+        SolverResult::Sat
+    );
+    assert_eq!(solver.solve_assumps(&[g1]).unwrap(), SolverResult::Unsat);
+    // reusing the identifier of a removed group creates a new group
+    removable
+        .add_clause(1, clause![!lit![1]], &mut solver, &mut var_manager)
+        .unwrap();
+    assert_ne!(removable.activate(1).unwrap(), g1);
+    assert_eq!(
+        solver.solve_assumps(&removable.assumps()).unwrap(),
+        SolverResult::Unsat
+    );
+}
+
+#[test]
+fn query_session_sequence() {
+    let mut session = QuerySession::new(rustsat_minisat::core::Minisat::default());
+    let solver = session.solver_mut();
+    solver.add_binary(lit![0], lit![1]).unwrap();
+    solver.add_binary(!lit![1], lit![2]).unwrap();
+    solver.add_binary(!lit![2], !lit![3]).unwrap();
+
+    let (res, sol, core) = session.query(&[!lit![0]]).unwrap();
+    assert_eq!(res, SolverResult::Sat);
+    assert!(core.is_none());
+    let sol = sol.unwrap();
+    assert_eq!(sol.lit_value(lit![1]), TernaryVal::True);
+    assert_eq!(sol.lit_value(lit![2]), TernaryVal::True);
+    assert_eq!(sol.lit_value(lit![3]), TernaryVal::False);
+
+    let (res, sol, core) = session.query(&[!lit![0], lit![3]]).unwrap();
+    assert_eq!(res, SolverResult::Unsat);
+    assert!(sol.is_none());
+    let core = core.unwrap();
+    assert!(!core.is_empty());
+    assert!(core.iter().all(|&l| l == lit![0] || l == !lit![3]));
+
+    let (res, sol, _) = session.query(&[lit![3]]).unwrap();
+    assert_eq!(res, SolverResult::Sat);
+    assert_eq!(sol.unwrap().lit_value(lit![0]), TernaryVal::True);
+
+    let (res, _, _) = session.query(&[lit![3], !lit![0]]).unwrap();
+    assert_eq!(res, SolverResult::Unsat);
+    assert_eq!(session.n_queries(), 4);
+}
+
+#[test]
+fn minimal_model_no_removable_lit() {
+    let mut instance: SatInstance = SatInstance::new();
+    instance.add_binary(lit![0], lit![1]);
+    instance.add_binary(lit![1], lit![2]);
+    instance.add_binary(lit![2], lit![3]);
+    instance.add_ternary(lit![0], lit![3], lit![4]);
+    let objective = [lit![0], lit![1], lit![2], lit![3], lit![4]];
+    let mut solver = rustsat_minisat::core::Minisat::default();
+    let model = instance
+        .solve_minimal_model(&mut solver, &objective)
+        .unwrap()
+        .unwrap();
+    assert!(instance.is_sat(&model));
+    let falsified: Vec<_> = objective
+        .iter()
+        .filter(|&&l| model.lit_value(l) == TernaryVal::False)
+        .map(|&l| !l)
+        .collect();
+    let (cnf, _) = instance.clone().as_cnf();
+    for &lit in &objective {
+        if model.lit_value(lit) != TernaryVal::True {
+            continue;
+        }
+        let mut checker = rustsat_minisat::core::Minisat::default();
+        checker.add_cnf(cnf.clone()).unwrap();
+        let mut assumps = falsified.clone();
+        assumps.push(!lit);
+        assert_eq!(
+            checker.solve_assumps(&assumps).unwrap(),
+            SolverResult::Unsat
+        );
+    }
+}
+
+#[test]
+fn minimal_model_unsat() {
+    let mut instance: SatInstance = SatInstance::new();
+    instance.add_unit(lit![0]);
+    instance.add_unit(!lit![0]);
+    let mut solver = rustsat_minisat::core::Minisat::default();
+    assert!(instance
+        .solve_minimal_model(&mut solver, &[lit![0]])
+        .unwrap()
+        .is_none());
+}
+
+#[test]
+fn minimal_model_empty_clause() {
+    let mut instance: SatInstance = SatInstance::new();
+    instance.add_clause(clause![]);
+    let mut solver = rustsat_minisat::core::Minisat::default();
+    assert!(instance
+        .solve_minimal_model(&mut solver, &[])
+        .unwrap()
+        .is_none());
+}
+
+#[test]
+fn relevant_assumptions_drop_entailed() {
+    let mut solver = rustsat_minisat::core::Minisat::default();
+    solver.add_binary(!lit![0], lit![1]).unwrap();
+    solver.add_binary(lit![2], lit![3]).unwrap();
+    let assumps = [lit![0], lit![1], lit![2]];
+    assert_eq!(solver.solve_assumps(&assumps).unwrap(), SolverResult::Sat);
+    // `lit![1]` is entailed by `lit![0]`
+    let relevant = solver.relevant_assumptions(&assumps).unwrap();
+    assert_eq!(relevant, vec![lit![0], lit![2]]);
+    assert_eq!(solver.lit_val(lit![1]).unwrap(), TernaryVal::True);
+}
+
+#[test]
+fn relevant_assumptions_unsat() {
+    let mut solver = rustsat_minisat::core::Minisat::default();
+    solver.add_binary(lit![0], lit![1]).unwrap();
+    assert_eq!(solver.solve_assumps(&[lit![0]]).unwrap(), SolverResult::Sat);
+    assert!(matches!(
+        solver.relevant_assumptions(&[lit![0], !lit![0]]),
+        Err(SolverError::State(..))
+    ));
+}
+
+#[test]
+fn enumerate_projected_models() {
+    let mut solver = rustsat_minisat::core::Minisat::default();
+    solver.add_binary(lit![0], lit![1]).unwrap();
+    solver.add_binary(lit![1], lit![2]).unwrap();
+    let models: Vec<_> = solver
+        .enumerate(&[var![0], var![1]])
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(models.len(), 3);
+    for model in &models {
+        assert_eq!(model.var_value(var![2]), TernaryVal::DontCare);
+        assert!(
+            model.var_value(var![0]) == TernaryVal::True
+                || model.var_value(var![1]) == TernaryVal::True
+        );
+    }
+    // All models are blocked now
+    assert_eq!(solver.solve().unwrap(), SolverResult::Unsat);
+}
+
+#[test]
+fn enumerate_interrupted() {
+    let mut solver = rustsat_minisat::core::Minisat::default();
+    solver.add_binary(lit![0], lit![1]).unwrap();
+    solver.limit_propagations(Some(0)).unwrap();
+    let mut models = solver.enumerate(&[var![0], var![1]]);
+    assert!(matches!(models.next(), Some(Err(SolverError::Interrupted))));
+    assert!(models.next().is_none());
+}
+
+#[test]
+fn backbone() {
+    let mut solver = rustsat_minisat::core::Minisat::default();
+    solver.add_unit(lit![0]).unwrap();
+    solver.add_binary(!lit![0], lit![1]).unwrap();
+    solver.add_binary(lit![2], lit![3]).unwrap();
+    assert_eq!(solver.solve().unwrap(), SolverResult::Sat);
+    let candidates = [lit![0], !lit![1], lit![1], lit![2], !lit![2], lit![3]];
+    let backbone = compute_backbone(&mut solver, &candidates).unwrap();
+    assert_eq!(backbone, vec![lit![0], lit![1]]);
+}
+
+#[test]
+fn core_minimization() {
+    for method in [CoreMinimization::Deletion, CoreMinimization::QuickXplain] {
+        let mut solver = rustsat_minisat::core::Minisat::default();
+        solver.add_binary(!lit![0], !lit![1]).unwrap();
+        solver.add_unit(!lit![2]).unwrap();
+        // Entailed but not minimal
+        let core = vec![!lit![0], !lit![1], !lit![2], !lit![3]];
+        let mut min = minimize_core(&mut solver, core, method).unwrap();
+        min.sort();
+        assert!(min == vec![!lit![2]] || min == vec![!lit![0], !lit![1]]);
+    }
+}
+
+#[test]
+fn clause_recorder_origins() {
+    let mut recorder = ClauseRecorder::new(rustsat_minisat::core::Minisat::default());
+    recorder
+        .add_clause_with_origin(clause![lit![0], lit![1]], 0)
+        .unwrap();
+    recorder.add_binary(!lit![0], lit![2]).unwrap();
+    recorder
+        .add_clause_with_origin(clause![!lit![1], lit![2]], 1)
+        .unwrap();
+    recorder
+        .add_clause_with_origin(clause![!lit![2]], 1)
+        .unwrap();
+    assert_eq!(recorder.solve().unwrap(), SolverResult::Unsat);
+
+    let recorded = recorder.recorded();
+    assert_eq!(recorded.len(), 4);
+    assert_eq!(recorded[0], (clause![lit![0], lit![1]], Some(0)));
+    assert_eq!(recorded[1], (clause![!lit![0], lit![2]], None));
+    assert_eq!(recorded[2], (clause![!lit![1], lit![2]], Some(1)));
+    assert_eq!(recorded[3], (clause![!lit![2]], Some(1)));
+    assert_eq!(recorder.recorded_with_origin(1).count(), 2);
+    assert_eq!(recorder.recorded_with_origin(2).count(), 0);
+}
+
+#[test]
+fn wrappers_forward_duplicate_warning() {
+    let mut recorder = ClauseRecorder::new(rustsat_minisat::core::Minisat::default());
+    recorder.warn_duplicate_clauses(true).unwrap();
+    recorder.add_binary(lit![0], lit![1]).unwrap();
+    recorder.add_binary(lit![1], lit![0]).unwrap();
+    assert_eq!(recorder.solver().n_duplicate_clauses(), 1);
+
+    let mut stack = ClauseStack::new(rustsat_minisat::core::Minisat::default());
+    stack.warn_duplicate_clauses(true).unwrap();
+    stack.add_binary(lit![0], lit![1]).unwrap();
+    stack.add_binary(lit![1], lit![0]).unwrap();
+    assert_eq!(stack.solver().n_duplicate_clauses(), 1);
+}
+
+#[test]
+fn clause_recorder_propagate() {
+    let mut recorder = ClauseRecorder::new(rustsat_minisat::core::Minisat::default());
+    recorder.add_binary(!lit![0], lit![1]).unwrap();
+    recorder.add_ternary(!lit![1], !lit![2], lit![3]).unwrap();
+    recorder.add_binary(!lit![3], !lit![0]).unwrap();
+    assert_eq!(
+        recorder.propagate(vec![lit![0]]).unwrap(),
+        (vec![lit![0], lit![1], !lit![3], !lit![2]], false)
+    );
+    assert!(recorder.propagate(vec![lit![0], lit![2]]).unwrap().1);
+    assert!(recorder.propagate(vec![lit![3], !lit![3]]).unwrap().1);
+    assert_eq!(recorder.propagate(vec![]).unwrap(), (vec![], false));
+    // the recorded clauses are not changed by propagation
+    assert_eq!(recorder.recorded().len(), 3);
+}
+
+#[test]
+fn verify_query_results() {
+    let mut instance: SatInstance = SatInstance::new();
+    instance.add_binary(lit![0], lit![1]);
+    instance.add_binary(!lit![1], lit![2]);
+    let mut session = QuerySession::new(rustsat_minisat::core::Minisat::default());
+    session
+        .solver_mut()
+        .add_cnf(instance.clone().as_cnf().0)
+        .unwrap();
+    let factory = rustsat_minisat::core::Minisat::default;
+
+    let assumps = [!lit![0]];
+    let result = session.query(&assumps).unwrap();
+    assert!(verify_result(&instance, &assumps, &result, factory).is_ok());
+    // Corrupt the model
+    let (res, model, core) = result;
+    let mut model = model.unwrap();
+    model.assign_lit(!lit![1]);
+    let corrupted = (res, Some(model), core);
+    assert!(matches!(
+        verify_result(&instance, &assumps, &corrupted, factory),
+        Err(VerificationError::ModelNotSat)
+    ));
+
+    let assumps = [!lit![0], !lit![2], !lit![3]];
+    let result = session.query(&assumps).unwrap();
+    assert_eq!(result.0, SolverResult::Unsat);
+    assert!(verify_result(&instance, &assumps, &result, factory).is_ok());
+    // A core that is not implied by the instance
+    let wrong_core = (SolverResult::Unsat, None, Some(vec![lit![0], lit![3]]));
+    assert!(matches!(
+        verify_result(&instance, &assumps, &wrong_core, factory),
+        Err(VerificationError::CoreSat)
+    ));
+    // A core with literals that are not assumptions
+    let foreign_core = (SolverResult::Unsat, None, Some(vec![lit![4]]));
+    assert!(matches!(
+        verify_result(&instance, &assumps, &foreign_core, factory),
+        Err(VerificationError::CoreNotFromAssumptions(_))
+    ));
+}
+
+#[cfg(feature = "rand")]
+#[test]
+fn diverse_portfolio_model() {
+    use rustsat::solvers::DiversePortfolio;
+
+    let inst: SatInstance<BasicVarManager> =
+        SatInstance::from_dimacs_path("./data/AProVE11-12.cnf").unwrap();
+    let portfolio = DiversePortfolio::from_instance(inst.clone(), 3, 42);
+    assert_eq!(portfolio.n_variants(), 3);
+    let (res, model) = portfolio
+        .solve(rustsat_minisat::core::Minisat::default)
+        .unwrap();
+    assert_eq!(res, SolverResult::Sat);
+    assert!(inst.is_sat(&model.unwrap()));
+}
+
+#[cfg(feature = "rand")]
+#[test]
+fn diverse_portfolio_panicking_solver() {
+    use rustsat::solvers::DiversePortfolio;
+
+    let mut inst: SatInstance<BasicVarManager> = SatInstance::new();
+    inst.add_binary(lit![0], lit![1]);
+    let portfolio = DiversePortfolio::from_instance(inst, 2, 42);
+    assert!(matches!(
+        portfolio.solve(|| -> rustsat_minisat::core::Minisat { panic!("broken solver") }),
+        Err(SolverError::Api(_))
+    ));
+}
+
+#[test]
+fn portfolio_race() {
+    use rustsat::solvers::Portfolio;
+
+    let mut portfolio = Portfolio::default();
+    portfolio.add_solver(rustsat_minisat::core::Minisat::default);
+    portfolio.add_solver(rustsat_minisat::simp::Minisat::default);
+    portfolio.add_solver(|| -> rustsat_minisat::core::Minisat { panic!("broken solver") });
+    assert_eq!(portfolio.n_solvers(), 3);
+    portfolio.add_binary(lit![0], lit![1]).unwrap();
+    portfolio.add_unit(!lit![0]).unwrap();
+    assert_eq!(portfolio.solve().unwrap(), SolverResult::Sat);
+    assert_eq!(portfolio.lit_val(lit![1]).unwrap(), TernaryVal::True);
+    portfolio.add_unit(!lit![1]).unwrap();
+    assert_eq!(portfolio.solve().unwrap(), SolverResult::Unsat);
+    assert!(portfolio.lit_val(lit![1]).is_err());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn stats_to_json() {
+    use rustsat::solvers::SolveStats;
+
+    let mut solver = rustsat_minisat::core::Minisat::default();
+    solver.add_binary(lit![0], lit![1]).unwrap();
+    solver.add_ternary(!lit![0], lit![2], lit![3]).unwrap();
+    solver.add_unit(!lit![1]).unwrap();
+    assert_eq!(solver.solve().unwrap(), SolverResult::Sat);
+    let stats = solver.stats();
+    let json: serde_json::Value = serde_json::from_str(&stats.to_json()).unwrap();
+    assert_eq!(json["n_sat"], 1);
+    assert_eq!(json["n_unsat"], 0);
+    assert_eq!(json["n_clauses"], 3);
+    assert_eq!(json["max_var"], 3);
+    assert_eq!(json["avg_clause_len"], 2.0);
+    assert_eq!(
+        json["cpu_solve_time_secs"],
+        stats.cpu_solve_time.as_secs_f64()
+    );
+    assert_eq!(
+        json["cpu_solve_time"]["secs"],
+        stats.cpu_solve_time.as_secs()
+    );
+}
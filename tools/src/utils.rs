@@ -1,3 +1,11 @@
+use rustsat::{
+    instances::{BasicVarManager, SatInstance},
+    lit,
+    solvers::{PhaseLit, Solve, SolverResult},
+    types::TernaryVal,
+    var,
+};
+
 /// Test a solver under two sets of assumptions and assert that the result is as
 /// given. This is used in the integration tests.
 #[macro_export]
@@ -209,3 +217,28 @@ macro_rules! test_all {
         );
     }};
 }
+
+/// Tests warm starting a solver from the solution of another one via
+/// [`PhaseLit::phase_assignment`]. The instance `./data/small.cnf` is solved
+/// by `first` with phases forcing a specific solution, which `second` must
+/// find again after phasing it. This is used in the integration tests of the
+/// solver crates.
+pub fn test_warm_start<S: Solve + PhaseLit>(mut first: S, mut second: S) {
+    let inst: SatInstance<BasicVarManager> =
+        SatInstance::from_dimacs_path("./data/small.cnf").unwrap();
+    first.add_cnf(inst.clone().as_cnf().0).unwrap();
+    first.phase_lit(!lit![0]).unwrap();
+    first.phase_lit(lit![1]).unwrap();
+    first.phase_lit(!lit![2]).unwrap();
+    first.phase_lit(lit![3]).unwrap();
+    assert_eq!(first.solve().unwrap(), SolverResult::Sat);
+    let sol = first.solution(var![3]).unwrap();
+    second.add_cnf(inst.as_cnf().0).unwrap();
+    second.phase_assignment(&sol).unwrap();
+    assert_eq!(second.solve().unwrap(), SolverResult::Sat);
+    let warm = second.solution(var![3]).unwrap();
+    assert_eq!(warm.lit_value(lit![0]), TernaryVal::False);
+    assert_eq!(warm.lit_value(lit![1]), TernaryVal::True);
+    assert_eq!(warm.lit_value(lit![2]), TernaryVal::False);
+    assert_eq!(warm.lit_value(lit![3]), TernaryVal::True);
+}